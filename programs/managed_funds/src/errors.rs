@@ -31,4 +31,76 @@ pub enum FundError {
 
     #[msg("Inv Er")]
     InvocationFailed,
+
+    #[msg("Er treasury")]
+    InvalidTreasury,
+
+    #[msg("Er auth")]
+    Unauthorized,
+
+    #[msg("Er fee recipient")]
+    InvalidFeeRecipient,
+
+    #[msg("Er cap")]
+    WithdrawalCapExceeded,
+
+    #[msg("Er expiry")]
+    WithdrawalNotExpired,
+
+    #[msg("Er mode")]
+    InvalidRedemptionMode,
+
+    #[msg("Er epoch")]
+    InvalidEpoch,
+
+    #[msg("Er attestor")]
+    InvalidAttestor,
+
+    #[msg("Er stale")]
+    StaleAttestation,
+
+    #[msg("Er nav quorum")]
+    NavQuorumNotMet,
+
+    #[msg("Er nav stale")]
+    StaleNav,
+
+    #[msg("Er oracle")]
+    InvalidOracle,
+
+    #[msg("Er oracle stale")]
+    StaleOraclePrice,
+
+    #[msg("Er oracle conf")]
+    OracleConfidenceTooWide,
+
+    #[msg("Er deposits paused")]
+    DepositsPaused,
+
+    #[msg("Er risk")]
+    RiskPolicyViolation,
+
+    #[msg("Er swap balance")]
+    UnexpectedBalanceChange,
+
+    #[msg("Er swap program")]
+    InvalidSwapProgram,
+
+    #[msg("Er swap accounts")]
+    InvalidSwapAccounts,
+
+    #[msg("Er mint ext")]
+    UnsupportedMintExtension,
+
+    #[msg("Er decimals")]
+    InvalidDecimals,
+
+    #[msg("Er metadata")]
+    InvalidShareMetadata,
+
+    #[msg("Er fund version")]
+    InvalidFundVersion,
+
+    #[msg("Er holding acct")]
+    InvalidHoldingAccount,
 }
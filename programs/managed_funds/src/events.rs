@@ -0,0 +1,410 @@
+use anchor_lang::prelude::*;
+use crate::state::{MintListMode, RedemptionMode, SwapAdapter};
+
+// Events are emitted with `emit!` regardless of the `onchain-logs` feature so indexers can
+// follow every state change from transaction logs.
+
+#[event]
+pub struct FundCreated {
+    pub fund: Pubkey,
+    pub manager: Pubkey,
+    pub base_mint: Pubkey,
+    pub shares_mint: Pubkey,
+    pub management_fee: u16,
+    pub performance_fee: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Deposited {
+    pub fund: Pubkey,
+    pub investor: Pubkey,
+    pub amount: u64,
+    pub shares_minted: u64,
+    pub share_price: u64, // SHARE_PRICE_SCALE
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalInitiated {
+    pub fund: Pubkey,
+    pub investor: Pubkey,
+    pub shares: u64,
+    pub fraction_bps: u32, // 1e6 precision
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedemptionQueued {
+    pub fund: Pubkey,
+    pub investor: Pubkey,
+    pub shares: u64,
+    pub epoch: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalSwapLeg {
+    pub fund: Pubkey,
+    pub investor: Pubkey,
+    pub input_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalFinalized {
+    pub fund: Pubkey,
+    pub investor: Pubkey,
+    pub shares_burned: u64,
+    pub gross_amount: u64,
+    pub net_amount: u64,
+    pub manager_performance_fee: u64,
+    pub treasury_performance_fee: u64,
+    pub platform_withdrawal_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalClosed {
+    pub fund: Pubkey,
+    pub investor: Pubkey,
+    pub expired: bool, // false when cancelled by the investor
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedemptionEpochSettled {
+    pub fund: Pubkey,
+    pub epoch: u64,
+    pub shares_queued: u64,
+    pub shares_redeemed: u64,
+    pub payout_total: u64,
+    pub share_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedemptionClaimed {
+    pub fund: Pubkey,
+    pub investor: Pubkey,
+    pub epoch: u64,
+    pub shares_redeemed: u64,
+    pub shares_returned: u64,
+    pub gross_amount: u64,
+    pub net_amount: u64,
+    pub manager_performance_fee: u64,
+    pub treasury_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedeemedInKind {
+    pub fund: Pubkey,
+    pub investor: Pubkey,
+    pub shares_burned: u64,
+    pub fee_shares: u64,
+    pub value: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InvestorPaid {
+    pub fund: Pubkey,
+    pub investor: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayoutDistributed {
+    pub fund: Pubkey,
+    pub total_amount: u64,
+    pub platform_fee: u64,
+    pub treasury_performance_fee: u64,
+    pub manager_performance_fee: u64,
+    pub recipients: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NavUpdated {
+    pub fund: Pubkey,
+    pub attestor: Pubkey, // default for oracle refreshes
+    pub nav_value: u64,
+    pub accepted: bool,   // false when the circuit breaker tripped
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ManagementFeeCollected {
+    pub fund: Pubkey,
+    pub manager: Pubkey,
+    pub shares_minted: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultSwap {
+    pub fund: Pubkey,
+    pub sequence: u64, // Trade record number
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ManagerTokenTransfer {
+    pub fund: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ManagerLamportTransfer {
+    pub fund: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenAccountsClosed {
+    pub fund: Pubkey,
+    pub destination: Pubkey,
+    pub accounts_closed: u16,
+    pub positions_closed: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WsolUnwrapped {
+    pub fund: Pubkey,
+    pub account: Pubkey,
+    pub destination: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SharesTransferred {
+    pub fund: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub shares: u64,
+    pub cost_basis: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FundMigrated {
+    pub fund: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub old_len: u32,
+    pub new_len: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultPositionMigrated {
+    pub fund: Pubkey,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub old_len: u32,
+    pub new_len: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LegacyWithdrawalClosed {
+    pub fund: Pubkey,
+    pub investor: Pubkey,
+    pub progress_closed: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NavAttestationMigrated {
+    pub fund: Pubkey,
+    pub nav_attestation: Pubkey,
+    pub old_len: u32,
+    pub new_len: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PositionMigrated {
+    pub fund: Pubkey,
+    pub investor: Pubkey,
+    pub high_water_mark: u64,
+    pub old_len: u32,
+    pub new_len: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NavConfirmed {
+    pub fund: Pubkey,
+    pub authority: Pubkey,
+    pub nav_value: u64,
+    pub share_price: u64, // new circuit-breaker reference (SHARE_PRICE_SCALE)
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NavAttestorSet {
+    pub fund: Pubkey,
+    pub previous_attestor: Pubkey,
+    pub attestor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolConfigUpdated {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub platform_fee_bps: u16,
+    pub treasury_performance_share_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FundTokenApproved {
+    pub fund: Pubkey,
+    pub account: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FundTokenRevoked {
+    pub fund: Pubkey,
+    pub account: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalReady {
+    pub fund: Pubkey,
+    pub investor: Pubkey,
+    pub sol_accumulated: u64,
+    pub input_liquidated_sum: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RiskPolicySet {
+    pub fund: Pubkey,
+    pub mint_list_mode: MintListMode,
+    pub mints: Vec<Pubkey>,
+    pub max_position_bps: u16,
+    pub max_trade_bps: u16,
+    pub max_trades_per_day: u16,
+    pub effective_at: i64, // equals `timestamp` unless the change loosens the policy and is staged
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SwapProgramSet {
+    pub program: Pubkey,
+    pub adapter: SwapAdapter,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PositionOpened {
+    pub fund: Pubkey,
+    pub investor: Pubkey,
+    pub payer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ShareMetadataUriUpdated {
+    pub fund: Pubkey,
+    pub metadata: Pubkey,
+    pub uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NavCircuitBreakerSet {
+    pub fund: Pubkey,
+    pub max_nav_change_bps: u16,
+    pub nav_window_secs: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NavQuorumSet {
+    pub fund: Pubkey,
+    pub attestors: Vec<Pubkey>,
+    pub threshold: u8,
+    pub tolerance_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OraclePricingSet {
+    pub fund: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedemptionModeSet {
+    pub fund: Pubkey,
+    pub mode: RedemptionMode,
+    pub redemption_gate_bps: u16,
+    pub epoch_duration_secs: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalTimeoutSet {
+    pub fund: Pubkey,
+    pub timeout_secs: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OracleFeedUpdated {
+    pub mint: Pubkey,
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolConfigInitialized {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub platform_fee_bps: u16,
+    pub treasury_performance_share_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NavHistoryInitialized {
+    pub fund: Pubkey,
+    pub nav_history: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultRepaired {
+    pub fund: Pubkey,
+    pub vault: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    #[account(
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump
+    )]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"withdrawal", fund.key().as_ref(), investor.key().as_ref()],
+        bump = withdrawal_state.bump,
+        has_one = investor,
+        constraint = withdrawal_state.vault == fund.key(),
+        constraint = withdrawal_state.status.can_transition_to(&WithdrawalStatus::Failed) @ FundError::InvalidWithdrawalStatus,
+        close = investor
+    )]
+    pub withdrawal_state: Account<'info, WithdrawalState>,
+
+    #[account(mut)]
+    pub investor: Signer<'info>,
+}
+
+/// Abandon a withdrawal before finalization. No shares are burned; proceeds of any executed legs
+/// stay in the fund. Remaining accounts: WithdrawalMintProgress PDAs to close (rent goes to the investor).
+pub fn cancel_withdrawal<'info>(ctx: Context<'_, '_, 'info, 'info, CancelWithdrawal<'info>>) -> Result<()> {
+    WithdrawalState::close_progress_accounts(
+        &ctx.accounts.withdrawal_state.key(),
+        ctx.remaining_accounts,
+        &ctx.accounts.investor.to_account_info(),
+    )?;
+
+    emit!(WithdrawalClosed {
+        fund: ctx.accounts.fund.key(),
+        investor: ctx.accounts.investor.key(),
+        expired: false,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, TokenInterface, TokenAccount, Mint, MintTo, TransferChecked};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct ClaimRedemption<'info> {
+    #[account(
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump
+    )]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        seeds = [b"epoch", fund.key().as_ref(), &withdrawal_state.epoch.to_le_bytes()],
+        bump = redemption_epoch.bump,
+        constraint = redemption_epoch.status == EpochStatus::Settled @ FundError::InvalidEpoch
+    )]
+    pub redemption_epoch: Account<'info, RedemptionEpoch>,
+
+    #[account(
+        mut,
+        seeds = [b"withdrawal", fund.key().as_ref(), investor.key().as_ref()],
+        bump = withdrawal_state.bump,
+        has_one = investor,
+        constraint = withdrawal_state.vault == fund.key(),
+        constraint = withdrawal_state.status == WithdrawalStatus::Queued @ FundError::InvalidWithdrawalStatus,
+        close = investor
+    )]
+    pub withdrawal_state: Account<'info, WithdrawalState>,
+
+    #[account(
+        mut,
+        seeds = [b"position", investor.key().as_ref(), fund.key().as_ref()],
+        bump,
+        has_one = investor,
+        has_one = fund
+    )]
+    pub investor_position: Account<'info, InvestorPosition>,
+
+    #[account(
+        mut,
+        seeds = [b"shares", fund.key().as_ref()],
+        bump = fund.shares_bump,
+        mint::authority = fund,
+        mint::token_program = shares_token_program
+    )]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = shares_mint,
+        token::authority = investor,
+        token::token_program = shares_token_program
+    )]
+    pub investor_shares_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"redemption_vault", fund.key().as_ref()],
+        bump,
+        token::mint = fund.base_mint,
+        token::authority = fund
+    )]
+    pub redemption_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = fund.base_mint,
+        token::authority = investor
+    )]
+    pub investor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        token::mint = fund.base_mint,
+        constraint = treasury_token_account.owner == protocol_config.treasury @ FundError::InvalidTreasury
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = fund.base_mint,
+        constraint = manager_token_account.owner == fund.manager @ FundError::InvalidFeeRecipient
+    )]
+    pub manager_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    #[account(address = fund.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token program of the shares mint (Token-2022 for funds created with transferable shares)
+    pub shares_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Claim a queued redemption once its epoch has settled: the redeemed part is paid from the
+/// redemption vault net of fees, and shares cut by the redemption gate are minted back.
+pub fn claim_redemption(ctx: Context<ClaimRedemption>) -> Result<()> {
+    let clock = Clock::get()?;
+    let fund = &ctx.accounts.fund;
+    let epoch = &ctx.accounts.redemption_epoch;
+    let ws = &ctx.accounts.withdrawal_state;
+    let config = &ctx.accounts.protocol_config;
+
+    let queued = ws.shares_to_withdraw;
+    let redeemed = epoch.redeemed_portion(queued);
+    let returned = queued - redeemed;
+    let gross = epoch.payout_for(redeemed);
+
+    // Performance fee only on value above the mark held when the request was queued
+    let basis = (redeemed as u128 * ws.high_water_mark as u128 / SHARE_PRICE_SCALE) as u64;
+    let profit = gross.saturating_sub(basis);
+    // Funds created before the cap was enforced at initialization are charged at most the cap
+    let performance_fee_bps = fund.performance_fee.min(Fund::MAX_PERFORMANCE_FEE_BPS);
+    let performance_fee = (profit as u128 * performance_fee_bps as u128 / 10000) as u64;
+    let platform_performance_fee = config.treasury_performance_cut(performance_fee);
+    let manager_performance_fee = performance_fee - platform_performance_fee;
+    let platform_withdrawal_fee = config.platform_fee(gross);
+    let treasury_fee = platform_performance_fee + platform_withdrawal_fee;
+    let net = gross
+        .checked_sub(performance_fee + platform_withdrawal_fee)
+        .ok_or(FundError::MathOverflow)?;
+
+    let fund_seeds = &[
+        b"fund",
+        fund.manager.as_ref(),
+        fund.name.as_bytes(),
+        &[fund.bump],
+    ];
+    let signer = &[&fund_seeds[..]];
+
+    for (to, amount) in [
+        (ctx.accounts.investor_token_account.to_account_info(), net),
+        (ctx.accounts.manager_token_account.to_account_info(), manager_performance_fee),
+        (ctx.accounts.treasury_token_account.to_account_info(), treasury_fee),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.redemption_vault.to_account_info(),
+                mint: ctx.accounts.base_mint.to_account_info(),
+                to,
+                authority: fund.to_account_info(),
+            },
+            signer,
+        );
+        token_interface::transfer_checked(transfer_ctx, amount, ctx.accounts.base_mint.decimals)?;
+    }
+
+    // Shares cut by the gate were never redeemed and are still counted in fund.total_shares
+    if returned > 0 {
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.shares_token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.shares_mint.to_account_info(),
+                to: ctx.accounts.investor_shares_account.to_account_info(),
+                authority: fund.to_account_info(),
+            },
+            signer,
+        );
+        token_interface::mint_to(mint_ctx, returned)?;
+    }
+
+    let high_water_mark = ws.high_water_mark;
+    let investor_position = &mut ctx.accounts.investor_position;
+    if returned > 0 {
+        investor_position.record_entry(returned, high_water_mark)?;
+        investor_position.shares = investor_position.shares.checked_add(returned).ok_or(FundError::MathOverflow)?;
+    }
+    investor_position.total_withdrawn = investor_position.total_withdrawn.checked_add(net).ok_or(FundError::MathOverflow)?;
+    investor_position.last_activity_at = clock.unix_timestamp;
+    if investor_position.shares == 0 {
+        investor_position.high_water_mark = 0;
+    }
+
+    ctx.accounts.withdrawal_state.transition_to(WithdrawalStatus::Completed)?;
+
+    emit!(RedemptionClaimed {
+        fund: ctx.accounts.fund.key(),
+        investor: ctx.accounts.investor.key(),
+        epoch: ctx.accounts.redemption_epoch.index,
+        shares_redeemed: redeemed,
+        shares_returned: returned,
+        gross_amount: gross,
+        net_amount: net,
+        manager_performance_fee,
+        treasury_fee,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
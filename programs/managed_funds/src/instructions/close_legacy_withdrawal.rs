@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct CloseLegacyWithdrawal<'info> {
+    #[account(
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump
+    )]
+    pub fund: Box<Account<'info, Fund>>,
+
+    /// CHECK: a WithdrawalState opened before per-mint allowances, which no longer deserializes;
+    /// owner and PDA are checked here, discriminator, length and owner fields in the handler
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"withdrawal", fund.key().as_ref(), investor.key().as_ref()],
+        bump
+    )]
+    pub withdrawal_state: UncheckedAccount<'info>,
+
+    /// Owner of the withdrawal; receives the rent of every closed account
+    #[account(mut)]
+    pub investor: Signer<'info>,
+}
+
+/// Close a withdrawal opened under the layout without per-mint allowances, together with its
+/// WithdrawalMintProgress PDAs (passed as remaining accounts), refunding their rent to the investor.
+/// Shares are only burned at finalization, so none are held by the request: whatever its legs
+/// liquidated stays in the fund and the investor can initiate a new withdrawal right away.
+pub fn close_legacy_withdrawal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseLegacyWithdrawal<'info>>,
+) -> Result<()> {
+    let withdrawal_ai = ctx.accounts.withdrawal_state.to_account_info();
+    let withdrawal_key = withdrawal_ai.key();
+    {
+        let data = withdrawal_ai.try_borrow_data()?;
+        require!(data.len() >= 72 && data[..8] == *WithdrawalState::DISCRIMINATOR, FundError::InvalidInput);
+        require!(data.len() < WithdrawalState::SPACE, FundError::InvalidFundVersion);
+        // investor and vault lead the layout in every version
+        require!(data[8..40] == ctx.accounts.investor.key().to_bytes(), FundError::Unauthorized);
+        require!(data[40..72] == ctx.accounts.fund.key().to_bytes(), FundError::InvalidInput);
+    }
+
+    let investor_ai = ctx.accounts.investor.to_account_info();
+    let mut progress_closed: u8 = 0;
+    for progress_ai in ctx.remaining_accounts.iter() {
+        require_keys_eq!(*progress_ai.owner, crate::ID, FundError::InvalidInput);
+        {
+            let data = progress_ai.try_borrow_data()?;
+            require!(
+                data.len() >= 40 && data[..8] == *WithdrawalMintProgress::DISCRIMINATOR,
+                FundError::InvalidInput
+            );
+            require!(data.len() < WithdrawalMintProgress::SPACE, FundError::InvalidFundVersion);
+            require!(data[8..40] == withdrawal_key.to_bytes(), FundError::InvalidInput);
+        }
+        close_raw(progress_ai, &investor_ai)?;
+        progress_closed = progress_closed.checked_add(1).ok_or(FundError::MathOverflow)?;
+    }
+    close_raw(&withdrawal_ai, &investor_ai)?;
+
+    emit!(LegacyWithdrawalClosed {
+        fund: ctx.accounts.fund.key(),
+        investor: ctx.accounts.investor.key(),
+        progress_closed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Close a program account that cannot be loaded as its Anchor type: move its lamports to
+/// `destination` and hand the emptied account back to the system program.
+fn close_raw<'info>(account: &AccountInfo<'info>, destination: &AccountInfo<'info>) -> Result<()> {
+    let lamports = account.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(FundError::MathOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+    account.assign(&anchor_lang::system_program::ID);
+    account.resize(0)?;
+    Ok(())
+}
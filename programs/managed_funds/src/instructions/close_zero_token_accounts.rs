@@ -1,9 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::{TokenInterface, TokenAccount};
 use anchor_spl::token::spl_token as spl_token;
-use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token_2022::spl_token_2022 as spl_token_2022;
 
 use crate::state::Fund as FundState;
+use crate::state::VaultPosition;
+use crate::errors::FundError;
+use crate::events::*;
 
 // Closes all provided zero-balance SPL token accounts owned by the Fund PDA.
 // The lamports recovered from closing accounts are sent to `fund_wsol_ata`.
@@ -12,20 +16,34 @@ use crate::state::Fund as FundState;
 //
 // Pass any number of token accounts to close in `remaining_accounts`.
 // Safety checks:
-// - Skips accounts that are not owned by the SPL token program
+// - Skips accounts that are not owned by the SPL token or Token-2022 program
+//   (closing Token-2022 accounts needs that program among the transaction accounts,
+//   e.g. passed as `token_program` or in `remaining_accounts`)
 // - Skips accounts whose owner is not the Fund PDA
 // - Skips accounts with non-zero amount
 // - Skips if the account matches `fund_wsol_ata`
-pub fn close_zero_token_accounts<'info>(ctx: Context<'_, '_, '_, 'info, CloseZeroTokenAccounts<'info>>) -> Result<()> {
+//
+// The fund's ATA of a non-base mint is the canonical holding account of that mint's VaultPosition,
+// so it must be immediately followed by the VaultPosition PDA of the same mint (registered or not).
+// A registered entry is closed together with its account (rent also goes to `fund_wsol_ata`), so the
+// registry never lists a mint whose holding account is gone.
+pub fn close_zero_token_accounts<'info>(ctx: Context<'_, '_, 'info, 'info, CloseZeroTokenAccounts<'info>>) -> Result<()> {
     let fund = &ctx.accounts.fund;
     let _token_program = &ctx.accounts.token_program;
     let fund_wsol_ata = &ctx.accounts.fund_wsol_ata;
 
     let mut closed_any = false;
+    let mut positions_closed: u8 = 0;
+    let mut accounts_closed: u16 = 0;
+
+    let accounts = ctx.remaining_accounts;
+    let mut i = 0;
+    while i < accounts.len() {
+        let acc_info = &accounts[i];
+        i += 1;
 
-    for acc_info in ctx.remaining_accounts.iter() {
         // Only process token accounts
-        if acc_info.owner != &token::ID {
+        if acc_info.owner != &spl_token::ID && acc_info.owner != &spl_token_2022::ID {
             continue;
         }
 
@@ -36,8 +54,8 @@ pub fn close_zero_token_accounts<'info>(ctx: Context<'_, '_, '_, 'info, CloseZer
 
         // Parse token account
         let data = acc_info.try_borrow_data()?;
-        // spl_token state Account unpack; use the re-exported spl_token from anchor_spl
-        let ta = match spl_token::state::Account::unpack(&data) {
+        // Token-2022 layout is a superset of the legacy one, so one parser covers both programs
+        let ta = match TokenAccount::try_deserialize(&mut &data[..]) {
             Ok(a) => a,
             Err(_) => continue,
         };
@@ -49,9 +67,26 @@ pub fn close_zero_token_accounts<'info>(ctx: Context<'_, '_, '_, 'info, CloseZer
 
         drop(data); // release borrow before CPI
 
+        // A canonical holding account takes its registry entry with it
+        let holding = get_associated_token_address_with_program_id(&fund.key(), &ta.mint, acc_info.owner);
+        if *acc_info.key == holding && ta.mint != fund.base_mint {
+            let position_ai = accounts.get(i).ok_or(FundError::InvalidHoldingAccount)?;
+            i += 1;
+            let (expected, _) = Pubkey::find_program_address(
+                &[b"vault_position", fund.key().as_ref(), ta.mint.as_ref()],
+                ctx.program_id,
+            );
+            require_keys_eq!(position_ai.key(), expected, FundError::InvalidHoldingAccount);
+            if position_ai.owner == ctx.program_id && !position_ai.data_is_empty() {
+                let position = Account::<VaultPosition>::try_from(position_ai)?;
+                position.close(fund_wsol_ata.to_account_info())?;
+                positions_closed = positions_closed.checked_add(1).ok_or(FundError::MathOverflow)?;
+            }
+        }
+
         // Build and invoke close_account instruction
-        let ix = spl_token::instruction::close_account(
-            &token::ID,
+        let ix = spl_token_2022::instruction::close_account(
+            acc_info.owner,
             acc_info.key,
             &fund_wsol_ata.key(),
             &fund.key(),
@@ -69,18 +104,24 @@ pub fn close_zero_token_accounts<'info>(ctx: Context<'_, '_, '_, 'info, CloseZer
             ],
             &[signer_seeds],
         )?;
+        accounts_closed = accounts_closed.saturating_add(1);
         closed_any = true;
     }
 
+    if positions_closed > 0 {
+        let fund = &mut ctx.accounts.fund;
+        fund.total_positions = fund.total_positions.saturating_sub(positions_closed);
+    }
+
     // If destination is native mint ATA, sync native so token amount reflects added lamports
     if closed_any {
         // We need to ensure this is a native mint account before syncing; read mint from account data
         let dest_ai = fund_wsol_ata.to_account_info();
         let dest_data = dest_ai.try_borrow_data()?;
-        if let Ok(dest_ta) = spl_token::state::Account::unpack(&dest_data) {
+        if let Ok(dest_ta) = TokenAccount::try_deserialize(&mut &dest_data[..]) {
             if dest_ta.mint == spl_token::native_mint::id() {
                 drop(dest_data);
-                let ix_sync = spl_token::instruction::sync_native(&token::ID, &fund_wsol_ata.key())?;
+                let ix_sync = spl_token::instruction::sync_native(&spl_token::ID, &fund_wsol_ata.key())?;
                 anchor_lang::solana_program::program::invoke(
                     &ix_sync,
                     &[dest_ai],
@@ -89,6 +130,16 @@ pub fn close_zero_token_accounts<'info>(ctx: Context<'_, '_, '_, 'info, CloseZer
         }
     }
 
+    if closed_any {
+        emit!(TokenAccountsClosed {
+            fund: ctx.accounts.fund.key(),
+            destination: ctx.accounts.fund_wsol_ata.key(),
+            accounts_closed,
+            positions_closed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
     Ok(())
 }
 
@@ -98,6 +149,6 @@ pub struct CloseZeroTokenAccounts<'info> {
     pub fund: Account<'info, FundState>,
     /// Destination for recovered lamports; typically the Fund's WSOL ATA
     #[account(mut)]
-    pub fund_wsol_ata: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    pub fund_wsol_ata: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, TokenInterface, TokenAccount, Mint, MintTo};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct CollectManagementFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump,
+        has_one = manager
+    )]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"shares", fund.key().as_ref()],
+        bump = fund.shares_bump,
+        mint::authority = fund
+    )]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+
+    /// Manager's position; fee shares are tracked like any other holding so they can be withdrawn
+    #[account(
+        init_if_needed,
+        payer = manager,
+        space = InvestorPosition::SPACE,
+        seeds = [b"position", manager.key().as_ref(), fund.key().as_ref()],
+        bump
+    )]
+    pub manager_position: Account<'info, InvestorPosition>,
+
+    #[account(
+        init_if_needed,
+        payer = manager,
+        associated_token::mint = shares_mint,
+        associated_token::authority = manager,
+        associated_token::token_program = token_program
+    )]
+    pub manager_shares_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn collect_management_fee(ctx: Context<CollectManagementFee>) -> Result<()> {
+    let clock = Clock::get()?;
+    let fund = &mut ctx.accounts.fund;
+
+    // Bring the accrual up to date, then mint everything owed so far
+    fund.accrue_management_fee(clock.unix_timestamp)?;
+    let fee_shares = fund.pending_fee_shares;
+    if fee_shares == 0 {
+        return Ok(());
+    }
+
+    let fund_seeds = &[
+        b"fund",
+        fund.manager.as_ref(),
+        fund.name.as_bytes(),
+        &[fund.bump],
+    ];
+    let signer = &[&fund_seeds[..]];
+
+    let mint_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.shares_mint.to_account_info(),
+            to: ctx.accounts.manager_shares_account.to_account_info(),
+            authority: fund.to_account_info(),
+        },
+        signer,
+    );
+    token_interface::mint_to(mint_ctx, fee_shares)?;
+
+    // Shares were already counted in total_shares at accrual time
+    fund.pending_fee_shares = 0;
+
+    let manager_position = &mut ctx.accounts.manager_position;
+    if manager_position.investor == Pubkey::default() {
+        manager_position.investor = ctx.accounts.manager.key();
+        manager_position.fund = fund.key();
+        manager_position.shares = fee_shares;
+        manager_position.initial_investment = 0;
+        manager_position.total_deposited = 0;
+        manager_position.total_withdrawn = 0;
+        manager_position.first_deposit_at = clock.unix_timestamp;
+        manager_position.last_activity_at = clock.unix_timestamp;
+        manager_position.high_water_mark = fund.share_price()?;
+    } else {
+        manager_position.record_entry(fee_shares, fund.share_price()?)?;
+        manager_position.shares = manager_position.shares.checked_add(fee_shares).ok_or(FundError::MathOverflow)?;
+        manager_position.last_activity_at = clock.unix_timestamp;
+    }
+
+    emit!(ManagementFeeCollected {
+        fund: fund.key(),
+        manager: ctx.accounts.manager.key(),
+        shares_minted: fee_shares,
+        timestamp: clock.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct ConfirmNav<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump,
+        constraint = authority.key() == fund.manager || authority.key() == fund.nav_attestor @ FundError::Unauthorized
+    )]
+    pub fund: Account<'info, Fund>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Accept the share price at `nav_value` as the new circuit-breaker reference and resume deposits.
+/// Subsequent attestations are measured against the confirmed price.
+pub fn confirm_nav(ctx: Context<ConfirmNav>, nav_value: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let fund = &mut ctx.accounts.fund;
+    fund.nav_window_start = now;
+    fund.nav_window_price = fund.price_at(nav_value);
+    fund.deposits_paused = false;
+
+    emit!(NavConfirmed {
+        fund: fund.key(),
+        authority: ctx.accounts.authority.key(),
+        nav_value,
+        share_price: fund.nav_window_price,
+        timestamp: now,
+    });
+    Ok(())
+}
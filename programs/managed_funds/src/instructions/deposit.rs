@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_lang::AccountDeserialize;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer, MintTo};
+use anchor_spl::token_interface::{self, TokenInterface, TokenAccount, Mint, TransferChecked, MintTo};
 use anchor_spl::associated_token::AssociatedToken;
 use crate::state::*;
 use crate::errors::*;
+use crate::events::*;
 
 #[derive(Accounts)]
 pub struct Deposit<'info> {
@@ -22,15 +23,16 @@ pub struct Deposit<'info> {
         token::authority = fund
         
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         seeds = [b"shares", fund.key().as_ref()],
         bump = fund.shares_bump,
-        mint::authority = fund
+        mint::authority = fund,
+        mint::token_program = shares_token_program
     )]
-    pub shares_mint: Account<'info, Mint>,
+    pub shares_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init_if_needed,
@@ -46,64 +48,70 @@ pub struct Deposit<'info> {
         token::mint = fund.base_mint,
         token::authority = investor
     )]
-    pub investor_token_account: Account<'info, TokenAccount>,
+    pub investor_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init_if_needed,
         payer = investor,
         associated_token::mint = shares_mint,
-        associated_token::authority = investor
+        associated_token::authority = investor,
+        associated_token::token_program = shares_token_program
     )]
-    pub investor_shares_account: Account<'info, TokenAccount>,
+    pub investor_shares_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(mut)]
     pub investor: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"nav_history", fund.key().as_ref()],
+        bump
+    )]
+    pub nav_history: AccountLoader<'info, NavHistory>,
+
+    #[account(address = fund.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token program of the shares mint (Token-2022 for funds created with transferable shares)
+    pub shares_token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
     require!(amount > 0, FundError::InvalidAmount);
+    require!(!ctx.accounts.fund.deposits_paused, FundError::DepositsPaused);
 
     let fund = &mut ctx.accounts.fund;
     let investor_position = &mut ctx.accounts.investor_position;
     let clock = Clock::get()?;
 
-    // Optionally refresh NAV from a NavAttestation passed as a remaining account.
-    // This preserves the instruction signature and flow; investors can include nav_attest_write
-    // in the same transaction before calling deposit.
-    if let Some(nav_ai) = ctx.remaining_accounts.get(0) {
-        // Deserialize NavAttestation directly from raw data to avoid lifetime issues
-        let mut data_slice: &[u8] = &nav_ai.data.borrow();
-        if let Ok(nav_att) = NavAttestation::try_deserialize(&mut data_slice) {
-            if nav_att.fund == fund.key() && nav_att.expires_at >= clock.unix_timestamp {
-                // Safety: only accept NAV that is not below existing recorded NAV or base-mint vault balance
-                let floor = fund
-                    .total_assets
-                    .max(ctx.accounts.vault.amount);
-                if nav_att.nav_value >= floor {
-                    fund.total_assets = nav_att.nav_value;
-                }
-            }
-        }
+    if !refresh_deposit_nav(fund, ctx.accounts.vault.amount, ctx.remaining_accounts, ctx.program_id, clock.unix_timestamp)? {
+        crate::log!("deposit: breaker tripped, deposits paused");
+        return Ok(());
     }
 
+    // Settle the management fee against the refreshed NAV so the entry price is net of it
+    fund.accrue_management_fee(clock.unix_timestamp)?;
+
     // Calculate shares to mint based on current fund valuation
     let shares_to_mint = fund.calculate_shares_to_mint(amount);
+    require!(shares_to_mint > 0, FundError::InvalidAmount);
+    let entry_price = fund.share_price()?;
 
     // Transfer tokens from investor to fund vault
     let transfer_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.investor_token_account.to_account_info(),
+            mint: ctx.accounts.base_mint.to_account_info(),
             to: ctx.accounts.vault.to_account_info(),
             authority: ctx.accounts.investor.to_account_info(),
         },
     );
-    token::transfer(transfer_ctx, amount)?;
+    token_interface::transfer_checked(transfer_ctx, amount, ctx.accounts.base_mint.decimals)?;
 
     // Mint shares to investor
     let fund_seeds = &[
@@ -115,7 +123,7 @@ pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
     let signer = &[&fund_seeds[..]];
 
     let mint_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.shares_token_program.to_account_info(),
         MintTo {
             mint: ctx.accounts.shares_mint.to_account_info(),
             to: ctx.accounts.investor_shares_account.to_account_info(),
@@ -123,29 +131,102 @@ pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
         },
         signer,
     );
-    token::mint_to(mint_ctx, shares_to_mint)?;
+    token_interface::mint_to(mint_ctx, shares_to_mint)?;
 
     // Update fund state
     fund.total_assets = fund.total_assets.checked_add(amount).ok_or(FundError::MathOverflow)?;
     fund.total_shares = fund.total_shares.checked_add(shares_to_mint).ok_or(FundError::MathOverflow)?;
 
-    // Update investor position
-    if investor_position.investor == Pubkey::default() {
-        // First deposit for this investor
-        investor_position.investor = ctx.accounts.investor.key();
-        investor_position.fund = fund.key();
+    record_deposit(investor_position, ctx.accounts.investor.key(), fund.key(), shares_to_mint, amount, entry_price, clock.unix_timestamp)?;
+
+    ctx.accounts.nav_history.load_mut()?.record(&ctx.accounts.fund, clock.unix_timestamp)?;
+
+    emit!(Deposited {
+        fund: ctx.accounts.fund.key(),
+        investor: ctx.accounts.investor.key(),
+        amount,
+        shares_minted: shares_to_mint,
+        share_price: entry_price,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// NAV refresh shared by the deposit instructions; `vault_amount` is the vault balance before the deposit.
+/// Returns false when a quorum NAV tripped the circuit breaker: the deposit must then be skipped
+/// without failing, so the pause it set is kept.
+pub(crate) fn refresh_deposit_nav(
+    fund: &mut Account<Fund>,
+    vault_amount: u64,
+    remaining_accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    now: i64,
+) -> Result<bool> {
+    // Optionally refresh NAV from a NavAttestation passed as a remaining account.
+    // This preserves the instruction signature and flow; investors can include nav_attest_write
+    // in the same transaction before depositing. A supplied attestation must be the fund's
+    // NAV PDA, signed by its configured attestor and unexpired.
+    // Quorum funds must instead pass [NavQuorum, NavAttestation...] with every attestor's NAV PDA
+    // and are priced at the median, under the same floor and circuit breaker;
+    // oracle funds must have run refresh_nav beforehand.
+    if fund.nav_source == NavSource::Oracle {
+        fund.require_fresh_nav(now)?;
+    } else if fund.nav_source == NavSource::Quorum {
+        let nav = NavQuorum::nav_from_accounts(
+            &fund.key(),
+            remaining_accounts,
+            program_id,
+            now,
+        )?;
+        return Ok(fund.apply_quorum_nav(nav, vault_amount, now));
+    } else if let Some(nav_ai) = remaining_accounts.first() {
+        let (expected, _) = Pubkey::find_program_address(&[b"nav", fund.key().as_ref()], program_id);
+        require_keys_eq!(nav_ai.key(), expected, FundError::InvalidAttestor);
+        require_keys_eq!(*nav_ai.owner, *program_id, FundError::InvalidAttestor);
+        // Deserialize NavAttestation directly from raw data to avoid lifetime issues
+        let mut data_slice: &[u8] = &nav_ai.data.borrow();
+        let nav_att = NavAttestation::try_deserialize(&mut data_slice)?;
+        require!(
+            nav_att.is_valid_for(&fund.key(), &fund.nav_attestor, now),
+            FundError::StaleAttestation
+        );
+        // Safety: only accept NAV that is not below existing recorded NAV or base-mint vault balance
+        let floor = fund.total_assets.max(vault_amount);
+        if nav_att.nav_value >= floor {
+            fund.total_assets = nav_att.nav_value;
+        }
+    }
+    Ok(true)
+}
+
+/// Credit `shares_to_mint` bought for `amount` at `entry_price` to the investor's position.
+pub(crate) fn record_deposit(
+    investor_position: &mut InvestorPosition,
+    investor: Pubkey,
+    fund: Pubkey,
+    shares_to_mint: u64,
+    amount: u64,
+    entry_price: u64,
+    now: i64,
+) -> Result<()> {
+    if investor_position.shares == 0 && investor_position.total_deposited == 0 {
+        // First deposit for this investor (or into a position opened to receive shares)
+        investor_position.investor = investor;
+        investor_position.fund = fund;
         investor_position.shares = shares_to_mint;
         investor_position.initial_investment = amount;
         investor_position.total_deposited = amount;
         investor_position.total_withdrawn = 0;
-        investor_position.first_deposit_at = clock.unix_timestamp;
-        investor_position.last_activity_at = clock.unix_timestamp;
+        investor_position.first_deposit_at = now;
+        investor_position.last_activity_at = now;
+        investor_position.high_water_mark = entry_price;
     } else {
         // Subsequent deposit
+        investor_position.record_entry(shares_to_mint, entry_price)?;
         investor_position.shares = investor_position.shares.checked_add(shares_to_mint).ok_or(FundError::MathOverflow)?;
         investor_position.total_deposited = investor_position.total_deposited.checked_add(amount).ok_or(FundError::MathOverflow)?;
-        investor_position.last_activity_at = clock.unix_timestamp;
+        investor_position.last_activity_at = now;
     }
-
     Ok(())
 }
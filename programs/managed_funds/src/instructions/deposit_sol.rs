@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{self, TokenInterface, TokenAccount, Mint, MintTo, SyncNative};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::instructions::deposit::{record_deposit, refresh_deposit_nav};
+
+#[derive(Accounts)]
+pub struct DepositSol<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump,
+        constraint = fund.base_mint == native_mint::id() @ FundError::InvalidMint
+    )]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", fund.key().as_ref()],
+        bump = fund.vault_bump,
+        token::mint = fund.base_mint,
+        token::authority = fund,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"shares", fund.key().as_ref()],
+        bump = fund.shares_bump,
+        mint::authority = fund,
+        mint::token_program = shares_token_program
+    )]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = investor,
+        space = InvestorPosition::SPACE,
+        seeds = [b"position", investor.key().as_ref(), fund.key().as_ref()],
+        bump
+    )]
+    pub investor_position: Account<'info, InvestorPosition>,
+
+    #[account(
+        init_if_needed,
+        payer = investor,
+        associated_token::mint = shares_mint,
+        associated_token::authority = investor,
+        associated_token::token_program = shares_token_program
+    )]
+    pub investor_shares_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Pays the deposit in lamports from its system account
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"nav_history", fund.key().as_ref()],
+        bump
+    )]
+    pub nav_history: AccountLoader<'info, NavHistory>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token program of the shares mint (Token-2022 for funds created with transferable shares)
+    pub shares_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Deposit `amount` lamports into a WSOL fund: the lamports are wrapped straight into the vault
+/// and priced exactly like `deposit`, including the optional NAV attestation remaining accounts.
+pub fn deposit_sol(ctx: Context<DepositSol>, amount: u64) -> Result<()> {
+    require!(amount > 0, FundError::InvalidAmount);
+    require!(!ctx.accounts.fund.deposits_paused, FundError::DepositsPaused);
+
+    let fund = &mut ctx.accounts.fund;
+    let investor_position = &mut ctx.accounts.investor_position;
+    let clock = Clock::get()?;
+
+    if !refresh_deposit_nav(fund, ctx.accounts.vault.amount, ctx.remaining_accounts, ctx.program_id, clock.unix_timestamp)? {
+        crate::log!("deposit_sol: breaker tripped, deposits paused");
+        return Ok(());
+    }
+
+    // Settle the management fee against the refreshed NAV so the entry price is net of it
+    fund.accrue_management_fee(clock.unix_timestamp)?;
+
+    let shares_to_mint = fund.calculate_shares_to_mint(amount);
+    require!(shares_to_mint > 0, FundError::InvalidAmount);
+    let entry_price = fund.share_price()?;
+
+    // Move lamports into the vault and let the token program count them as WSOL
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            SystemTransfer {
+                from: ctx.accounts.investor.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    token_interface::sync_native(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        SyncNative { account: ctx.accounts.vault.to_account_info() },
+    ))?;
+
+    let fund_seeds = &[
+        b"fund",
+        fund.manager.as_ref(),
+        fund.name.as_bytes(),
+        &[fund.bump],
+    ];
+    let signer = &[&fund_seeds[..]];
+
+    let mint_ctx = CpiContext::new_with_signer(
+        ctx.accounts.shares_token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.shares_mint.to_account_info(),
+            to: ctx.accounts.investor_shares_account.to_account_info(),
+            authority: fund.to_account_info(),
+        },
+        signer,
+    );
+    token_interface::mint_to(mint_ctx, shares_to_mint)?;
+
+    fund.total_assets = fund.total_assets.checked_add(amount).ok_or(FundError::MathOverflow)?;
+    fund.total_shares = fund.total_shares.checked_add(shares_to_mint).ok_or(FundError::MathOverflow)?;
+
+    record_deposit(investor_position, ctx.accounts.investor.key(), fund.key(), shares_to_mint, amount, entry_price, clock.unix_timestamp)?;
+
+    ctx.accounts.nav_history.load_mut()?.record(&ctx.accounts.fund, clock.unix_timestamp)?;
+
+    emit!(Deposited {
+        fund: ctx.accounts.fund.key(),
+        investor: ctx.accounts.investor.key(),
+        amount,
+        shares_minted: shares_to_mint,
+        share_price: entry_price,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
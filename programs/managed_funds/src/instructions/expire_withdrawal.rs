@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct ExpireWithdrawal<'info> {
+    #[account(
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump
+    )]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"withdrawal", fund.key().as_ref(), investor.key().as_ref()],
+        bump = withdrawal_state.bump,
+        has_one = investor,
+        constraint = withdrawal_state.vault == fund.key(),
+        close = investor
+    )]
+    pub withdrawal_state: Account<'info, WithdrawalState>,
+
+    /// CHECK: Investor wallet receiving the rent refund; bound by has_one on withdrawal_state
+    #[account(mut)]
+    pub investor: AccountInfo<'info>,
+
+    // Anyone (e.g. a keeper) may expire a stale withdrawal
+    pub payer: Signer<'info>,
+}
+
+/// Expire a withdrawal older than the fund's timeout. It is marked Failed and closed, and rent
+/// is refunded to the investor. Remaining accounts: WithdrawalMintProgress PDAs to close.
+pub fn expire_withdrawal<'info>(ctx: Context<'_, '_, 'info, 'info, ExpireWithdrawal<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let ws = &mut ctx.accounts.withdrawal_state;
+    let deadline = ws
+        .created_at
+        .checked_add(ctx.accounts.fund.withdrawal_timeout_secs)
+        .ok_or(FundError::MathOverflow)?;
+    require!(now >= deadline, FundError::WithdrawalNotExpired);
+    ws.transition_to(WithdrawalStatus::Failed)?;
+
+    WithdrawalState::close_progress_accounts(
+        &ws.key(),
+        ctx.remaining_accounts,
+        &ctx.accounts.investor,
+    )?;
+
+    emit!(WithdrawalClosed {
+        fund: ctx.accounts.fund.key(),
+        investor: ctx.accounts.investor.key(),
+        expired: true,
+        timestamp: now,
+    });
+    Ok(())
+}
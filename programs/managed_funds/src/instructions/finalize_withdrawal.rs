@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Burn};
+use anchor_spl::token_interface::{self, TokenInterface, TokenAccount, Mint, Burn};
 use crate::state::*;
 use crate::errors::*;
+use crate::events::*;
 
 #[derive(Accounts)]
 pub struct FinalizeWithdrawal<'info> {
@@ -27,14 +28,14 @@ pub struct FinalizeWithdrawal<'info> {
         bump = fund.shares_bump,
         mint::authority = fund
     )]
-    pub shares_mint: Account<'info, anchor_spl::token::Mint>,
+    pub shares_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         token::mint = shares_mint,
         token::authority = investor
     )]
-    pub investor_shares_account: Account<'info, TokenAccount>,
+    pub investor_shares_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
@@ -42,7 +43,7 @@ pub struct FinalizeWithdrawal<'info> {
         bump = withdrawal_state.bump,
         has_one = investor,
         constraint = withdrawal_state.vault == fund.key(),
-        constraint = withdrawal_state.status == WithdrawalStatus::ReadyToFinalize || withdrawal_state.status == WithdrawalStatus::Initiated,
+        constraint = withdrawal_state.status == WithdrawalStatus::ReadyToFinalize @ FundError::InvalidWithdrawalStatus,
         close = investor
     )]
     pub withdrawal_state: Account<'info, WithdrawalState>,
@@ -52,75 +53,78 @@ pub struct FinalizeWithdrawal<'info> {
     #[account(mut)]
     pub investor: Signer<'info>,
 
-    /// CHECK: This is the trader's wallet address from the fund
+    /// CHECK: Receives the manager's performance fee; must be the fund manager's wallet
+    #[account(mut, address = fund.manager @ FundError::InvalidFeeRecipient)]
     pub trader: AccountInfo<'info>,
 
-    /// CHECK: This is the treasury wallet address
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Treasury wallet; must match the protocol config
+    #[account(mut, address = protocol_config.treasury @ FundError::InvalidTreasury)]
     pub treasury: AccountInfo<'info>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(
+        mut,
+        seeds = [b"nav_history", fund.key().as_ref()],
+        bump
+    )]
+    pub nav_history: AccountLoader<'info, NavHistory>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn finalize_withdrawal(ctx: Context<FinalizeWithdrawal>) -> Result<()> {
+/// Remaining accounts: the WithdrawalMintProgress PDA of every non-zero allowance, in snapshot order.
+/// Progress accounts that exist must be writable; they are closed to the investor.
+pub fn finalize_withdrawal<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeWithdrawal<'info>>) -> Result<()> {
     let clock = Clock::get()?;
 
+    // Settle the management fee before the exit is priced
+    ctx.accounts.fund.accrue_management_fee(clock.unix_timestamp)?;
+
     // Read-only snapshots to avoid borrow conflicts
     let ws = &ctx.accounts.withdrawal_state;
     let ip = &ctx.accounts.investor_position;
     let fund_ro = &ctx.accounts.fund;
-    let manager_key = fund_ro.manager;
-    let fund_name = fund_ro.name.clone();
-    let fund_bump = fund_ro.bump;
     let performance_fee_bps = fund_ro.performance_fee;
 
-    // Completion factor k in [0,1] based on actually liquidated input vs allowed input
-    let allowed_sum = ws.input_allowed_total_sum as u128;
-    let done_sum = ws.input_liquidated_sum as u128;
-    let (k_num, k_den) = if allowed_sum > 0 { (done_sum.min(allowed_sum), allowed_sum) } else { (0, 1) };
+    // Completion factor k in [0,1], computed per mint from the progress PDAs
+    let (k_num, k_den) = withdrawal_completion(
+        ws,
+        ctx.remaining_accounts,
+        &ctx.accounts.investor.to_account_info(),
+    )?;
 
     // Effective shares to burn: floor(shares_to_withdraw * k)
     let shares_to_burn_eff = ((ws.shares_to_withdraw as u128).saturating_mul(k_num) / k_den) as u64;
 
-    // Effective fraction (1e6 precision)
-    let fraction_bps_eff = ((ws.fraction_bps as u128).saturating_mul(k_num) / k_den) as u64;
-
-    // Base withdrawal amount: prefer actual accumulated SOL over theoretical vault fraction
-    let base_withdrawal_amount = if ws.sol_accumulated > 0 {
-        ws.sol_accumulated
-    } else if allowed_sum > 0 {
-        let vault_balance = fund_ro.to_account_info().lamports() as u128;
-        let portion = vault_balance.saturating_mul(fraction_bps_eff as u128) / 1_000_000u128;
-        portion as u64
-    } else {
-        0
-    };
+    // Only SOL measured by the liquidation legs is paid out; lamports merely sitting on the Fund PDA
+    // (rent, donations, other investors' proceeds) are never attributed to this withdrawal
+    let base_withdrawal_amount = ws.sol_accumulated;
 
-    // Calculate fees
-    let initial_investment = (ip.total_deposited as f64 * shares_to_burn_eff as f64 / ip.shares as f64) as u64;
-    let profit = if base_withdrawal_amount > initial_investment {
-        base_withdrawal_amount - initial_investment
-    } else {
-        0
-    };
+    // Calculate fees: performance fee applies only to value above the investor's high-water mark
+    let profit = ip.gain_above_mark(shares_to_burn_eff, base_withdrawal_amount);
 
-    // Fee calculations (default values)
-    // performance_fee_bps taken from fund_ro above
-    let platform_fee_bps = 100u16; // 1% platform fee
+    // Fee parameters come from the fund (performance) and the protocol config (platform, split)
+    let config = &ctx.accounts.protocol_config;
 
-    // Performance fee (20% of profit by default)
+    // Performance fee on profit above the mark
     let performance_fee = if profit > 0 {
         (profit as u128 * performance_fee_bps as u128 / 10000) as u64
     } else {
         0
     };
 
-    // Platform gets 20% of performance fee
-    let platform_performance_fee = performance_fee * 20 / 100;
+    // Platform gets its configured share of the performance fee
+    let platform_performance_fee = config.treasury_performance_cut(performance_fee);
     let trader_performance_fee = performance_fee - platform_performance_fee;
 
-    // Platform withdrawal fee (1% of total withdrawal)
-    let platform_withdrawal_fee = (base_withdrawal_amount as u128 * platform_fee_bps as u128 / 10000) as u64;
+    // Platform withdrawal fee on the total withdrawal
+    let platform_withdrawal_fee = config.platform_fee(base_withdrawal_amount);
 
     // Total fees
     let total_platform_fees = platform_performance_fee + platform_withdrawal_fee;
@@ -142,7 +146,7 @@ pub fn finalize_withdrawal(ctx: Context<FinalizeWithdrawal>) -> Result<()> {
             authority: ctx.accounts.investor.to_account_info(),
         },
     );
-    token::burn(burn_ctx, shares_to_burn_eff)?;
+    token_interface::burn(burn_ctx, shares_to_burn_eff)?;
 
     // Transfer SOL to investor (program-owned source): adjust lamports directly
     let vault_sol_account = &fund_ro.to_account_info();
@@ -172,10 +176,70 @@ pub fn finalize_withdrawal(ctx: Context<FinalizeWithdrawal>) -> Result<()> {
     investor_position.shares = investor_position.shares.checked_sub(shares_to_burn_eff).ok_or(FundError::MathOverflow)?;
     investor_position.total_withdrawn = investor_position.total_withdrawn.checked_add(final_withdrawal_amount).ok_or(FundError::MathOverflow)?;
     investor_position.last_activity_at = clock.unix_timestamp;
+    // The fee crystallized on the redeemed shares only; shares still held keep their mark
+    if investor_position.shares == 0 {
+        investor_position.high_water_mark = 0;
+    }
 
     // Update withdrawal state
     let withdrawal_state = &mut ctx.accounts.withdrawal_state;
-    withdrawal_state.status = WithdrawalStatus::Completed;
+    withdrawal_state.transition_to(WithdrawalStatus::Completed)?;
+
+    ctx.accounts.nav_history.load_mut()?.record(&ctx.accounts.fund, clock.unix_timestamp)?;
+
+    emit!(WithdrawalFinalized {
+        fund: ctx.accounts.fund.key(),
+        investor: ctx.accounts.investor.key(),
+        shares_burned: shares_to_burn_eff,
+        gross_amount: base_withdrawal_amount,
+        net_amount: final_withdrawal_amount,
+        manager_performance_fee: trader_performance_fee,
+        treasury_performance_fee: platform_performance_fee,
+        platform_withdrawal_fee,
+        timestamp: clock.unix_timestamp,
+    });
 
     Ok(())
 }
+
+/// Completion factor k = (num, den) of a withdrawal, evaluated per mint as liquidated / allowed.
+/// The most complete mint sets k: every leg's proceeds are already in `sol_accumulated`, so burning
+/// shares on the best leg never redeems more value than the shares burned, whereas any smaller factor
+/// would let an investor keep shares for value already paid out. The trade-off falls on the investor:
+/// shares are burned for the full k even for mints that were never (or only partly) liquidated, and
+/// the unsold slice of those holdings stays in the fund for the remaining holders. With no allowances
+/// (fund holds only its base asset) the withdrawal is complete as initiated.
+fn withdrawal_completion<'info>(
+    ws: &Account<'info, WithdrawalState>,
+    progress_accounts: &'info [AccountInfo<'info>],
+    investor: &AccountInfo<'info>,
+) -> Result<(u128, u128)> {
+    let open: Vec<&MintAllowance> = ws.allowances.iter().filter(|a| a.amount_allowed > 0).collect();
+    if open.is_empty() {
+        return Ok((1, 1));
+    }
+    require!(progress_accounts.len() == open.len(), FundError::InvalidInput);
+
+    let (mut k_num, mut k_den) = (0u128, 1u128);
+    for (allowance, progress_ai) in open.iter().zip(progress_accounts.iter()) {
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"withdrawal_mint", ws.key().as_ref(), allowance.mint.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(progress_ai.key(), expected, FundError::InvalidInput);
+        // A mint that was never liquidated has no progress account yet
+        if progress_ai.data_is_empty() {
+            continue;
+        }
+        // Closed below, which fails opaquely on a read-only account
+        require!(progress_ai.is_writable, FundError::InvalidInput);
+        let progress: Account<WithdrawalMintProgress> = Account::try_from(progress_ai)?;
+        let done = progress.amount_liquidated.min(allowance.amount_allowed) as u128;
+        let allowed = allowance.amount_allowed as u128;
+        if done * k_den > k_num * allowed {
+            (k_num, k_den) = (done, allowed);
+        }
+        progress.close(investor.clone())?;
+    }
+    Ok((k_num, k_den))
+}
@@ -1,9 +1,23 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint};
+use anchor_spl::metadata::{self, mpl_token_metadata, CreateMetadataAccountsV3, Metadata};
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, Mint, Token2022};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
 use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::instructions::share_transfer_hook::{share_transfer_extra_metas, EXTRA_ACCOUNT_METAS_SEED, SHARE_TRANSFER_EXTRA_METAS};
+use crate::mint_extensions::require_supported_mint;
+
+/// Wallet-facing metadata for the shares mint; the name is the fund name.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ShareMetadata {
+    pub symbol: String,
+    pub uri: String,
+}
 
 #[derive(Accounts)]
-#[instruction(name: String)]
+#[instruction(name: String, description: String, management_fee: u16, performance_fee: u16, share_decimals: Option<u8>)]
 pub struct InitializeFund<'info> {
     #[account(
         init,
@@ -19,29 +33,68 @@ pub struct InitializeFund<'info> {
         payer = manager,
         token::mint = base_mint,
         token::authority = fund,
+        token::token_program = token_program,
         seeds = [b"vault", fund.key().as_ref()],
         bump
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init,
         payer = manager,
-        mint::decimals = 6,
+        mint::decimals = share_decimals.unwrap_or(base_mint.decimals),
         mint::authority = fund,
+        mint::token_program = shares_token_program,
+        extensions::transfer_hook::authority = fund,
+        extensions::transfer_hook::program_id = crate::ID,
         seeds = [b"shares", fund.key().as_ref()],
         bump
     )]
-    pub shares_mint: Account<'info, Mint>,
+    pub shares_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    pub base_mint: Account<'info, Mint>,
+    /// Accounts the token program resolves for the shares transfer hook
+    /// CHECK: created here and written as an ExtraAccountMetaList
+    #[account(
+        init,
+        payer = manager,
+        space = ExtraAccountMetaList::size_of(SHARE_TRANSFER_EXTRA_METAS)?,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, shares_mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = manager,
+        space = NavHistory::SPACE,
+        seeds = [b"nav_history", fund.key().as_ref()],
+        bump
+    )]
+    pub nav_history: AccountLoader<'info, NavHistory>,
+
+    /// Legacy SPL or Token-2022 mint without extensions that break fund accounting
+    #[account(mint::token_program = token_program)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
 
     #[account(mut)]
     pub manager: Signer<'info>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Shares are Token-2022 so transfers run the transfer hook
+    pub shares_token_program: Program<'info, Token2022>,
     pub rent: Sysvar<'info, Rent>,
+
+    /// Metaplex metadata PDA of the shares mint; only needed when share metadata is requested
+    /// CHECK: created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata::ID.as_ref(), shares_mint.key().as_ref()],
+        bump,
+        seeds::program = metadata::ID
+    )]
+    pub share_metadata: Option<UncheckedAccount<'info>>,
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
 }
 
 pub fn initialize_fund(
@@ -50,7 +103,19 @@ pub fn initialize_fund(
     description: String,
     management_fee: u16,
     performance_fee: u16,
+    share_decimals: Option<u8>,
+    share_metadata: Option<ShareMetadata>,
 ) -> Result<()> {
+    require!(management_fee <= Fund::MAX_MANAGEMENT_FEE_BPS, FundError::InvalidFee);
+    require!(performance_fee <= Fund::MAX_PERFORMANCE_FEE_BPS, FundError::InvalidFee);
+    let base_decimals = ctx.accounts.base_mint.decimals;
+    let share_decimals = share_decimals.unwrap_or(base_decimals);
+    require!(
+        base_decimals <= Fund::MAX_BASE_DECIMALS && share_decimals <= Fund::MAX_SHARE_DECIMALS,
+        FundError::InvalidDecimals
+    );
+    require_supported_mint(&ctx.accounts.base_mint.to_account_info())?;
+
     let fund = &mut ctx.accounts.fund;
     let clock = Clock::get()?;
 
@@ -69,6 +134,100 @@ pub fn initialize_fund(
     fund.bump = ctx.bumps.fund;
     fund.vault_bump = ctx.bumps.vault;
     fund.shares_bump = ctx.bumps.shares_mint;
+    fund.pending_fee_shares = 0;
+    fund.total_positions = 0;
+    fund.withdrawal_timeout_secs = Fund::DEFAULT_WITHDRAWAL_TIMEOUT_SECS;
+    fund.redemption_mode = RedemptionMode::Instant;
+    fund.redemption_gate_bps = 10_000;
+    fund.epoch_duration_secs = 0;
+    fund.current_epoch = 0;
+    fund.nav_attestor = Pubkey::default();
+    fund.nav_source = NavSource::Attestor;
+    fund.nav_updated_at = 0;
+    fund.max_nav_change_bps = Fund::DEFAULT_MAX_NAV_CHANGE_BPS;
+    fund.nav_window_secs = Fund::DEFAULT_NAV_WINDOW_SECS;
+    fund.nav_window_start = clock.unix_timestamp;
+    fund.nav_window_price = 0;
+    fund.deposits_paused = false;
+    fund.trade_count = 0;
+    fund.has_risk_policy = false;
+    fund.base_decimals = base_decimals;
+    fund.share_decimals = share_decimals;
+    fund.version = Fund::CURRENT_VERSION;
+    // The opening price must fit in a u64: base decimals may exceed share decimals by at most 10
+    require!(fund.share_price().is_ok(), FundError::InvalidDecimals);
+
+    let metas: Vec<ExtraAccountMeta> = share_transfer_extra_metas(&fund.key())?;
+    ExtraAccountMetaList::init::<ExecuteInstruction>(
+        &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+        &metas,
+    )?;
+
+    if let Some(share_metadata) = share_metadata {
+        create_share_metadata(&ctx, share_metadata)?;
+    }
+
+    let fund = &ctx.accounts.fund;
+    let mut history = ctx.accounts.nav_history.load_init()?;
+    history.fund = fund.key();
+    history.record(fund, clock.unix_timestamp)?;
+
+    emit!(FundCreated {
+        fund: fund.key(),
+        manager: fund.manager,
+        base_mint: fund.base_mint,
+        shares_mint: fund.shares_mint,
+        management_fee,
+        performance_fee,
+        timestamp: clock.unix_timestamp,
+    });
 
     Ok(())
 }
+
+/// Create Metaplex metadata for the shares mint, signed by the fund PDA as mint and update authority.
+fn create_share_metadata(ctx: &Context<InitializeFund>, share_metadata: ShareMetadata) -> Result<()> {
+    let (Some(metadata_account), Some(metadata_program)) =
+        (&ctx.accounts.share_metadata, &ctx.accounts.token_metadata_program)
+    else {
+        return err!(FundError::InvalidShareMetadata);
+    };
+    let fund = &ctx.accounts.fund;
+    require!(
+        fund.name.len() <= mpl_token_metadata::MAX_NAME_LENGTH
+            && share_metadata.symbol.len() <= mpl_token_metadata::MAX_SYMBOL_LENGTH
+            && share_metadata.uri.len() <= mpl_token_metadata::MAX_URI_LENGTH,
+        FundError::InvalidShareMetadata
+    );
+
+    let bump = [fund.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"fund", fund.manager.as_ref(), fund.name.as_bytes(), &bump]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        metadata_program.to_account_info(),
+        CreateMetadataAccountsV3 {
+            metadata: metadata_account.to_account_info(),
+            mint: ctx.accounts.shares_mint.to_account_info(),
+            mint_authority: fund.to_account_info(),
+            payer: ctx.accounts.manager.to_account_info(),
+            update_authority: fund.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        signer_seeds,
+    );
+    metadata::create_metadata_accounts_v3(
+        cpi_ctx,
+        mpl_token_metadata::types::DataV2 {
+            name: fund.name.clone(),
+            symbol: share_metadata.symbol,
+            uri: share_metadata.uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        true,
+        true,
+        None,
+    )
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct InitializeNavHistory<'info> {
+    #[account(
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump,
+        has_one = manager,
+        constraint = fund.version == Fund::CURRENT_VERSION @ FundError::InvalidFundVersion
+    )]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        init,
+        payer = manager,
+        space = NavHistory::SPACE,
+        seeds = [b"nav_history", fund.key().as_ref()],
+        bump
+    )]
+    pub nav_history: AccountLoader<'info, NavHistory>,
+
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the NAV history for a fund initialized before history tracking existed.
+/// Such a fund must first be brought to the current layout with migrate_fund.
+pub fn initialize_nav_history(ctx: Context<InitializeNavHistory>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut history = ctx.accounts.nav_history.load_init()?;
+    history.fund = ctx.accounts.fund.key();
+    history.record(&ctx.accounts.fund, now)?;
+    emit!(NavHistoryInitialized {
+        fund: history.fund,
+        nav_history: ctx.accounts.nav_history.key(),
+        timestamp: now,
+    });
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::program::ManagedFunds;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = ProtocolConfig::SPACE,
+        seeds = [b"protocol_config"],
+        bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    // Only the program upgrade authority may create the config (prevents front-running the PDA)
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, ManagedFunds>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ FundError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_protocol_config(
+    ctx: Context<InitializeProtocolConfig>,
+    treasury: Pubkey,
+    platform_fee_bps: u16,
+    treasury_performance_share_bps: u16,
+) -> Result<()> {
+    require!(
+        ProtocolConfig::is_valid_fees(platform_fee_bps, treasury_performance_share_bps),
+        FundError::InvalidFee
+    );
+
+    let config = &mut ctx.accounts.protocol_config;
+    config.authority = ctx.accounts.authority.key();
+    config.treasury = treasury;
+    config.platform_fee_bps = platform_fee_bps;
+    config.treasury_performance_share_bps = treasury_performance_share_bps;
+    config.bump = ctx.bumps.protocol_config;
+    emit!(ProtocolConfigInitialized {
+        authority: config.authority,
+        treasury,
+        platform_fee_bps,
+        treasury_performance_share_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, TokenInterface, TokenAccount, Mint, Burn};
 use crate::state::*;
 use crate::errors::*;
+use crate::events::*;
 
 #[derive(Accounts)]
 pub struct InitiateWithdrawal<'info> {
@@ -12,6 +14,7 @@ pub struct InitiateWithdrawal<'info> {
     pub fund: Account<'info, Fund>,
 
     #[account(
+        mut,
         seeds = [b"position", investor.key().as_ref(), fund.key().as_ref()],
         bump,
         has_one = investor,
@@ -32,18 +35,49 @@ pub struct InitiateWithdrawal<'info> {
     pub investor: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+
+    // Epoch-mode funds only: the open epoch and the accounts needed to burn the queued shares
+    #[account(
+        mut,
+        seeds = [b"epoch", fund.key().as_ref(), &fund.current_epoch.to_le_bytes()],
+        bump = redemption_epoch.bump,
+        constraint = redemption_epoch.status == EpochStatus::Open @ FundError::InvalidEpoch
+    )]
+    pub redemption_epoch: Option<Account<'info, RedemptionEpoch>>,
+
+    #[account(
+        mut,
+        seeds = [b"shares", fund.key().as_ref()],
+        bump = fund.shares_bump
+    )]
+    pub shares_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = fund.shares_mint,
+        token::authority = investor
+    )]
+    pub investor_shares_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
-pub fn initiate_withdrawal(
-    ctx: Context<InitiateWithdrawal>,
+/// Instant mode: remaining accounts must list every registered position of the fund as pairs:
+/// [VaultPosition (writable), the position's canonical token account].
+/// Epoch mode: the shares are burned and queued into the open epoch; no remaining accounts.
+pub fn initiate_withdrawal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, InitiateWithdrawal<'info>>,
     shares_to_withdraw: u64,
 ) -> Result<()> {
     require!(shares_to_withdraw > 0, FundError::InvalidShares);
 
+    let clock = Clock::get()?;
+    // Settle the management fee so the withdrawal fraction is taken from the diluted share count
+    ctx.accounts.fund.accrue_management_fee(clock.unix_timestamp)?;
+
     let fund = &ctx.accounts.fund;
     let investor_position = &ctx.accounts.investor_position;
     let withdrawal_state = &mut ctx.accounts.withdrawal_state;
-    let clock = Clock::get()?;
 
     // Verify investor has enough shares
     require!(
@@ -51,14 +85,50 @@ pub fn initiate_withdrawal(
         FundError::InsufficientFunds
     );
 
-    // For now, simulate getting vault positions
-    // In a real implementation, this would fetch from vault positions
-    let total_positions = 0u8; // Will be updated when position tracking is implemented
+    if fund.redemption_mode == RedemptionMode::Epoch {
+        return queue_redemption(ctx, shares_to_withdraw, clock.unix_timestamp);
+    }
 
     let total_shares_snapshot = fund.total_shares;
     // Use 1e6 precision for fraction to minimize rounding errors
     let fraction_bps: u32 = if total_shares_snapshot == 0 { 0 } else { ((shares_to_withdraw as u128 * 1_000_000u128) / total_shares_snapshot as u128) as u32 };
 
+    // Snapshot the position registry: every registered mint must be provided exactly once.
+    // Each mint's liquidation allowance is the investor's fraction of the fund's current holding.
+    require!(
+        ctx.remaining_accounts.len() == fund.total_positions as usize * 2,
+        FundError::InvalidInput
+    );
+    let mut allowances: Vec<MintAllowance> = Vec::with_capacity(fund.total_positions as usize);
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let mut position: Account<VaultPosition> = Account::try_from(&pair[0])?;
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"vault_position", fund.key().as_ref(), position.mint.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(pair[0].key(), expected, FundError::InvalidInput);
+        require!(
+            !allowances.iter().any(|a| a.mint == position.mint),
+            FundError::InvalidInput
+        );
+
+        position.require_holding(&pair[1].key())?;
+        let token_account: InterfaceAccount<TokenAccount> = InterfaceAccount::try_from(&pair[1])?;
+        require_keys_eq!(token_account.owner, fund.key(), FundError::InvalidInput);
+        require_keys_eq!(token_account.mint, position.mint, FundError::InvalidMint);
+
+        position.refresh(token_account.amount, clock.unix_timestamp);
+        position.exit(ctx.program_id)?;
+        allowances.push(MintAllowance {
+            mint: position.mint,
+            amount_allowed: (token_account.amount as u128 * fraction_bps as u128 / 1_000_000u128) as u64,
+        });
+    }
+    let total_positions = fund.total_positions;
+    let input_allowed_total_sum = allowances
+        .iter()
+        .fold(0u64, |acc, a| acc.saturating_add(a.amount_allowed));
+
     **withdrawal_state = WithdrawalState {
         investor: ctx.accounts.investor.key(),
         vault: fund.key(),
@@ -69,12 +139,81 @@ pub fn initiate_withdrawal(
         positions_liquidated: 0,
         total_positions,
         sol_accumulated: 0,
-        input_allowed_total_sum: 0,
+        input_allowed_total_sum,
         input_liquidated_sum: 0,
         status: WithdrawalStatus::Initiated,
         created_at: clock.unix_timestamp,
         bump: ctx.bumps.withdrawal_state,
+        allowances,
+        epoch: 0,
+        high_water_mark: investor_position.high_water_mark,
+    };
+
+    emit!(WithdrawalInitiated {
+        fund: fund.key(),
+        investor: ctx.accounts.investor.key(),
+        shares: shares_to_withdraw,
+        fraction_bps,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Enqueue `shares` into the fund's open epoch. The shares are burned now so they cannot be
+/// transferred or queued twice, but stay in `fund.total_shares` until the epoch settles;
+/// any part cut by the redemption gate is re-minted at claim time.
+fn queue_redemption<'info>(
+    ctx: Context<'_, '_, 'info, 'info, InitiateWithdrawal<'info>>,
+    shares: u64,
+    now: i64,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+    let (Some(epoch), Some(shares_mint), Some(investor_shares_account), Some(token_program)) = (
+        accounts.redemption_epoch.as_mut(),
+        accounts.shares_mint.as_ref(),
+        accounts.investor_shares_account.as_ref(),
+        accounts.token_program.as_ref(),
+    ) else {
+        return err!(FundError::InvalidRedemptionMode);
     };
 
+    let burn_ctx = CpiContext::new(
+        token_program.to_account_info(),
+        Burn {
+            mint: shares_mint.to_account_info(),
+            from: investor_shares_account.to_account_info(),
+            authority: accounts.investor.to_account_info(),
+        },
+    );
+    token_interface::burn(burn_ctx, shares)?;
+
+    epoch.shares_queued = epoch.shares_queued.checked_add(shares).ok_or(FundError::MathOverflow)?;
+
+    let investor_position = &mut accounts.investor_position;
+    let high_water_mark = investor_position.high_water_mark;
+    investor_position.shares = investor_position.shares.checked_sub(shares).ok_or(FundError::MathOverflow)?;
+    investor_position.last_activity_at = now;
+
+    let fund = &accounts.fund;
+    let withdrawal_state = &mut accounts.withdrawal_state;
+    withdrawal_state.investor = accounts.investor.key();
+    withdrawal_state.vault = fund.key();
+    withdrawal_state.shares_to_withdraw = shares;
+    withdrawal_state.total_shares_snapshot = fund.total_shares;
+    withdrawal_state.status = WithdrawalStatus::Queued;
+    withdrawal_state.created_at = now;
+    withdrawal_state.bump = ctx.bumps.withdrawal_state;
+    withdrawal_state.epoch = epoch.index;
+    withdrawal_state.high_water_mark = high_water_mark;
+
+    emit!(RedemptionQueued {
+        fund: fund.key(),
+        investor: accounts.investor.key(),
+        shares,
+        epoch: epoch.index,
+        timestamp: now,
+    });
+
     Ok(())
 }
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct MarkWithdrawalReady<'info> {
+    #[account(
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump
+    )]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"withdrawal", fund.key().as_ref(), investor.key().as_ref()],
+        bump = withdrawal_state.bump,
+        has_one = investor,
+        constraint = withdrawal_state.vault == fund.key()
+    )]
+    pub withdrawal_state: Account<'info, WithdrawalState>,
+
+    pub investor: Signer<'info>,
+}
+
+// Investor signals that liquidation legs are done; no further swaps are accepted afterwards.
+pub fn mark_withdrawal_ready(ctx: Context<MarkWithdrawalReady>) -> Result<()> {
+    let ws = &mut ctx.accounts.withdrawal_state;
+    ws.transition_to(WithdrawalStatus::ReadyToFinalize)?;
+
+    emit!(WithdrawalReady {
+        fund: ctx.accounts.fund.key(),
+        investor: ws.investor,
+        sol_accumulated: ws.sol_accumulated,
+        input_liquidated_sum: ws.input_liquidated_sum,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+use anchor_lang::Discriminator;
+use anchor_spl::token_interface::Mint;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct MigrateFund<'info> {
+    /// CHECK: may not deserialize as the current Fund layout yet; owner, discriminator,
+    /// PDA and manager are checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub fund: UncheckedAccount<'info>,
+
+    /// Checked against fund.base_mint in the handler
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Checked against fund.shares_mint in the handler
+    pub shares_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Pays the rent for the grown account
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow a fund created under an older layout to `Fund::SPACE` and bring it to `Fund::CURRENT_VERSION`.
+/// New fields are appended, so the old data stays in place and the added bytes read as zero;
+/// fields whose zero value is not a valid setting get the defaults of a newly created fund.
+pub fn migrate_fund(ctx: Context<MigrateFund>) -> Result<()> {
+    let fund_ai = ctx.accounts.fund.to_account_info();
+    {
+        let data = fund_ai.try_borrow_data()?;
+        require!(data.len() >= 8 && data[..8] == *Fund::DISCRIMINATOR, FundError::InvalidFundVersion);
+    }
+
+    let old_len = fund_ai.data_len();
+    if old_len < Fund::SPACE {
+        let required = Rent::get()?.minimum_balance(Fund::SPACE);
+        let top_up = required.saturating_sub(fund_ai.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    SystemTransfer {
+                        from: ctx.accounts.manager.to_account_info(),
+                        to: fund_ai.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        fund_ai.resize(Fund::SPACE)?;
+    }
+
+    let mut fund = Fund::try_deserialize(&mut &fund_ai.try_borrow_data()?[..])?;
+    require_keys_eq!(fund.manager, ctx.accounts.manager.key(), FundError::Unauthorized);
+    let expected = Pubkey::create_program_address(
+        &[b"fund", fund.manager.as_ref(), fund.name.as_bytes(), &[fund.bump]],
+        ctx.program_id,
+    )
+    .map_err(|_| FundError::InvalidFundVersion)?;
+    require_keys_eq!(expected, fund_ai.key(), FundError::InvalidFundVersion);
+    require_keys_eq!(fund.base_mint, ctx.accounts.base_mint.key(), FundError::InvalidMint);
+    require_keys_eq!(fund.shares_mint, ctx.accounts.shares_mint.key(), FundError::InvalidMint);
+    require!(fund.version < Fund::CURRENT_VERSION, FundError::InvalidFundVersion);
+
+    let now = Clock::get()?.unix_timestamp;
+    let from_version = fund.version;
+    if from_version == 0 {
+        if fund.withdrawal_timeout_secs == 0 {
+            fund.withdrawal_timeout_secs = Fund::DEFAULT_WITHDRAWAL_TIMEOUT_SECS;
+        }
+        if fund.redemption_gate_bps == 0 {
+            fund.redemption_gate_bps = 10_000;
+        }
+        // Breaker fields were added together; a zero window means they never existed
+        if fund.nav_window_secs == 0 {
+            fund.nav_window_secs = Fund::DEFAULT_NAV_WINDOW_SECS;
+            fund.max_nav_change_bps = Fund::DEFAULT_MAX_NAV_CHANGE_BPS;
+            fund.nav_window_start = now;
+            fund.nav_window_price = fund.price_at(fund.total_assets);
+        }
+        // Decimals are fixed by the mints
+        fund.base_decimals = ctx.accounts.base_mint.decimals;
+        fund.share_decimals = ctx.accounts.shares_mint.decimals;
+    }
+    fund.version = Fund::CURRENT_VERSION;
+    fund.try_serialize(&mut &mut fund_ai.try_borrow_mut_data()?[..])?;
+
+    emit!(FundMigrated {
+        fund: fund_ai.key(),
+        from_version,
+        to_version: Fund::CURRENT_VERSION,
+        old_len: old_len as u32,
+        new_len: fund_ai.data_len() as u32,
+        timestamp: now,
+    });
+    Ok(())
+}
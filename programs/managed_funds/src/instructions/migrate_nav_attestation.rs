@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct MigrateNavAttestation<'info> {
+    #[account(
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump
+    )]
+    pub fund: Box<Account<'info, Fund>>,
+
+    /// CHECK: may not deserialize as the current NavAttestation layout yet; owner and PDA are
+    /// checked here, discriminator and length in the handler
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"nav", fund.key().as_ref()],
+        bump
+    )]
+    pub nav_attestation: UncheckedAccount<'info>,
+
+    /// Pays the rent for the grown account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow a fund's single-attestor NAV PDA written before attestations recorded their signer to
+/// `NavAttestation::SPACE`. Which key signed the old value is unknown, so its attestor stays unset:
+/// the value is ignored until the fund's attestor writes a new one through nav_attest_write.
+pub fn migrate_nav_attestation(ctx: Context<MigrateNavAttestation>) -> Result<()> {
+    let nav_ai = ctx.accounts.nav_attestation.to_account_info();
+    let old_len = nav_ai.data_len();
+    {
+        let data = nav_ai.try_borrow_data()?;
+        require!(data.len() >= 8 && data[..8] == *NavAttestation::DISCRIMINATOR, FundError::InvalidFundVersion);
+    }
+    require!(old_len < NavAttestation::SPACE, FundError::InvalidFundVersion);
+
+    let required = Rent::get()?.minimum_balance(NavAttestation::SPACE);
+    let top_up = required.saturating_sub(nav_ai.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                SystemTransfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: nav_ai.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    // The appended attestor reads as the default key
+    nav_ai.resize(NavAttestation::SPACE)?;
+    let attestation = NavAttestation::try_deserialize(&mut &nav_ai.try_borrow_data()?[..])?;
+    require_keys_eq!(attestation.fund, ctx.accounts.fund.key(), FundError::InvalidInput);
+
+    emit!(NavAttestationMigrated {
+        fund: attestation.fund,
+        nav_attestation: nav_ai.key(),
+        old_len: old_len as u32,
+        new_len: nav_ai.data_len() as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
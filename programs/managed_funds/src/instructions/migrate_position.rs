@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct MigratePosition<'info> {
+    #[account(
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump
+    )]
+    pub fund: Box<Account<'info, Fund>>,
+
+    /// CHECK: may not deserialize as the current InvestorPosition layout yet; owner, PDA and
+    /// discriminator are checked here and in the handler
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"position", investor.key().as_ref(), fund.key().as_ref()],
+        bump
+    )]
+    pub investor_position: UncheckedAccount<'info>,
+
+    /// CHECK: owner of the position; only used for the PDA seeds
+    pub investor: UncheckedAccount<'info>,
+
+    /// Pays the rent for the grown account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow a position opened before the high-water mark existed to `InvestorPosition::SPACE`.
+/// Its entry price is unknown, so the mark starts at the fund's current share price: gains made
+/// from here on are charged, earlier ones are not.
+pub fn migrate_position(ctx: Context<MigratePosition>) -> Result<()> {
+    let position_ai = ctx.accounts.investor_position.to_account_info();
+    let old_len = position_ai.data_len();
+    {
+        let data = position_ai.try_borrow_data()?;
+        require!(data.len() >= 8 && data[..8] == *InvestorPosition::DISCRIMINATOR, FundError::InvalidFundVersion);
+    }
+    require!(old_len < InvestorPosition::SPACE, FundError::InvalidFundVersion);
+
+    let required = Rent::get()?.minimum_balance(InvestorPosition::SPACE);
+    let top_up = required.saturating_sub(position_ai.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                SystemTransfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: position_ai.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    position_ai.resize(InvestorPosition::SPACE)?;
+
+    let mut position = InvestorPosition::try_deserialize(&mut &position_ai.try_borrow_data()?[..])?;
+    require_keys_eq!(position.fund, ctx.accounts.fund.key(), FundError::InvalidInput);
+    require_keys_eq!(position.investor, ctx.accounts.investor.key(), FundError::InvalidInput);
+    position.high_water_mark = ctx.accounts.fund.share_price()?;
+    position.try_serialize(&mut &mut position_ai.try_borrow_mut_data()?[..])?;
+
+    emit!(PositionMigrated {
+        fund: position.fund,
+        investor: position.investor,
+        high_water_mark: position.high_water_mark,
+        old_len: old_len as u32,
+        new_len: position_ai.data_len() as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+use anchor_lang::Discriminator;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::Mint;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct MigrateVaultPosition<'info> {
+    #[account(
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump
+    )]
+    pub fund: Box<Account<'info, Fund>>,
+
+    /// CHECK: may not deserialize as the current VaultPosition layout yet; owner, PDA and
+    /// discriminator are checked here and in the handler
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"vault_position", fund.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub vault_position: UncheckedAccount<'info>,
+
+    /// Mint of the position; its owner decides the token program of the canonical holding
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Pays the rent for the grown account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow a VaultPosition registered before canonical holdings to `VaultPosition::SPACE`, pinning
+/// its holding to the fund's associated token account for the mint, as new registrations do.
+pub fn migrate_vault_position(ctx: Context<MigrateVaultPosition>) -> Result<()> {
+    let position_ai = ctx.accounts.vault_position.to_account_info();
+    let old_len = position_ai.data_len();
+    {
+        let data = position_ai.try_borrow_data()?;
+        require!(data.len() >= 8 && data[..8] == *VaultPosition::DISCRIMINATOR, FundError::InvalidFundVersion);
+    }
+    require!(old_len < VaultPosition::SPACE, FundError::InvalidFundVersion);
+
+    let required = Rent::get()?.minimum_balance(VaultPosition::SPACE);
+    let top_up = required.saturating_sub(position_ai.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                SystemTransfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: position_ai.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    position_ai.resize(VaultPosition::SPACE)?;
+
+    let mut position = VaultPosition::try_deserialize(&mut &position_ai.try_borrow_data()?[..])?;
+    let fund_key = ctx.accounts.fund.key();
+    let mint_ai = ctx.accounts.mint.to_account_info();
+    require_keys_eq!(position.vault, fund_key, FundError::InvalidInput);
+    require_keys_eq!(position.mint, mint_ai.key(), FundError::InvalidMint);
+    position.token_account = get_associated_token_address_with_program_id(&fund_key, &mint_ai.key(), mint_ai.owner);
+    position.try_serialize(&mut &mut position_ai.try_borrow_mut_data()?[..])?;
+
+    emit!(VaultPositionMigrated {
+        fund: fund_key,
+        mint: position.mint,
+        token_account: position.token_account,
+        old_len: old_len as u32,
+        new_len: position_ai.data_len() as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -13,6 +13,38 @@ pub mod close_zero_token_accounts;
 pub mod withdraw_swap_instruction;
 pub mod unwrap_wsol_fund;
 pub mod repair_vault;
+pub mod collect_management_fee;
+pub mod initialize_protocol_config;
+pub mod update_protocol_config;
+pub mod mark_withdrawal_ready;
+pub mod cancel_withdrawal;
+pub mod expire_withdrawal;
+pub mod set_withdrawal_timeout;
+pub mod set_redemption_mode;
+pub mod settle_redemption_epoch;
+pub mod claim_redemption;
+pub mod redeem_in_kind;
+pub mod set_nav_attestor;
+pub mod set_nav_quorum;
+pub mod nav_quorum_attest;
+pub mod update_oracle_feed;
+pub mod set_oracle_pricing;
+pub mod refresh_nav;
+pub mod initialize_nav_history;
+pub mod set_nav_circuit_breaker;
+pub mod confirm_nav;
+pub mod set_risk_policy;
+pub mod set_swap_program;
+pub mod open_position;
+pub mod share_transfer_hook;
+pub mod update_share_metadata_uri;
+pub mod deposit_sol;
+pub mod migrate_fund;
+pub mod transfer_shares;
+pub mod migrate_position;
+pub mod migrate_vault_position;
+pub mod close_legacy_withdrawal;
+pub mod migrate_nav_attestation;
 
 // Re-export active instructions for Anchor codegen and client convenience
 pub use initialize_fund::*;
@@ -30,3 +62,35 @@ pub use close_zero_token_accounts::*;
 pub use withdraw_swap_instruction::*;
 pub use unwrap_wsol_fund::*;
 pub use repair_vault::*;
+pub use collect_management_fee::*;
+pub use initialize_protocol_config::*;
+pub use update_protocol_config::*;
+pub use mark_withdrawal_ready::*;
+pub use cancel_withdrawal::*;
+pub use expire_withdrawal::*;
+pub use set_withdrawal_timeout::*;
+pub use set_redemption_mode::*;
+pub use settle_redemption_epoch::*;
+pub use claim_redemption::*;
+pub use redeem_in_kind::*;
+pub use set_nav_attestor::*;
+pub use set_nav_quorum::*;
+pub use nav_quorum_attest::*;
+pub use update_oracle_feed::*;
+pub use set_oracle_pricing::*;
+pub use refresh_nav::*;
+pub use initialize_nav_history::*;
+pub use set_nav_circuit_breaker::*;
+pub use confirm_nav::*;
+pub use set_risk_policy::*;
+pub use set_swap_program::*;
+pub use open_position::*;
+pub use share_transfer_hook::*;
+pub use update_share_metadata_uri::*;
+pub use deposit_sol::*;
+pub use migrate_fund::*;
+pub use transfer_shares::*;
+pub use migrate_position::*;
+pub use migrate_vault_position::*;
+pub use close_legacy_withdrawal::*;
+pub use migrate_nav_attestation::*;
@@ -1,14 +1,19 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
 
 #[derive(Accounts)]
 pub struct NavAttestWrite<'info> {
     #[account(
+        mut,
         seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
-        bump = fund.bump
+        bump = fund.bump,
+        constraint = fund.nav_attestor != Pubkey::default() @ FundError::InvalidAttestor
     )]
     pub fund: Account<'info, Fund>,
 
+    /// A PDA created under the layout without `attestor` must first run migrate_nav_attestation
     #[account(
         init_if_needed,
         payer = payer,
@@ -18,6 +23,10 @@ pub struct NavAttestWrite<'info> {
     )]
     pub nav_attestation: Account<'info, NavAttestation>,
 
+    /// The fund's configured NAV attestor
+    #[account(address = fund.nav_attestor @ FundError::InvalidAttestor)]
+    pub attestor: Signer<'info>,
+
     #[account(mut)]
     pub payer: Signer<'info>,
 
@@ -25,13 +34,26 @@ pub struct NavAttestWrite<'info> {
 }
 
 pub fn nav_attest_write(ctx: Context<NavAttestWrite>, nav_value: u64, expires_at: i64) -> Result<()> {
-    // Write attestation
     let now = Clock::get()?.unix_timestamp;
-    let att = &mut ctx.accounts.nav_attestation;
-    att.fund = ctx.accounts.fund.key();
-    att.nav_value = nav_value;
-    att.expires_at = expires_at;
-    att.updated_at = now;
-    att.bump = ctx.bumps.nav_attestation;
-    Ok(())
+    // A move beyond the circuit breaker pauses deposits and the value is not recorded
+    let accepted = ctx.accounts.fund.check_nav_move(nav_value, now);
+    emit!(NavUpdated {
+        fund: ctx.accounts.fund.key(),
+        attestor: ctx.accounts.attestor.key(),
+        nav_value,
+        accepted,
+        timestamp: now,
+    });
+    if !accepted {
+        crate::log!("nav_attest_write: breaker tripped, deposits paused");
+        return Ok(());
+    }
+    ctx.accounts.nav_attestation.record(
+        ctx.accounts.fund.key(),
+        ctx.accounts.attestor.key(),
+        nav_value,
+        expires_at,
+        now,
+        ctx.bumps.nav_attestation,
+    )
 }
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct NavQuorumAttest<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump
+    )]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        seeds = [b"nav_quorum", fund.key().as_ref()],
+        bump = nav_quorum.bump,
+        constraint = nav_quorum.attestors.contains(&attestor.key()) @ FundError::InvalidAttestor
+    )]
+    pub nav_quorum: Account<'info, NavQuorum>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = NavAttestation::SPACE,
+        seeds = [b"nav", fund.key().as_ref(), attestor.key().as_ref()],
+        bump,
+    )]
+    pub nav_attestation: Account<'info, NavAttestation>,
+
+    pub attestor: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn nav_quorum_attest(ctx: Context<NavQuorumAttest>, nav_value: u64, expires_at: i64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    // A move beyond the circuit breaker pauses deposits and the value is not recorded
+    let accepted = ctx.accounts.fund.check_nav_move(nav_value, now);
+    emit!(NavUpdated {
+        fund: ctx.accounts.fund.key(),
+        attestor: ctx.accounts.attestor.key(),
+        nav_value,
+        accepted,
+        timestamp: now,
+    });
+    if !accepted {
+        crate::log!("nav_quorum_attest: breaker tripped, deposits paused");
+        return Ok(());
+    }
+    ctx.accounts.nav_attestation.record(
+        ctx.accounts.fund.key(),
+        ctx.accounts.attestor.key(),
+        nav_value,
+        expires_at,
+        now,
+        ctx.bumps.nav_attestation,
+    )
+}
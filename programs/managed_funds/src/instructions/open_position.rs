@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump
+    )]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = InvestorPosition::SPACE,
+        seeds = [b"position", investor.key().as_ref(), fund.key().as_ref()],
+        bump
+    )]
+    pub investor_position: Account<'info, InvestorPosition>,
+
+    /// CHECK: any wallet that will hold fund shares
+    pub investor: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Open an empty InvestorPosition so `investor` can receive transferred shares.
+/// Permissionless; usually bundled by the sender right before a plain Token-2022 transfer
+/// (transfer_shares opens it on demand).
+pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+    let position = &mut ctx.accounts.investor_position;
+    position.investor = ctx.accounts.investor.key();
+    position.fund = ctx.accounts.fund.key();
+    position.last_activity_at = Clock::get()?.unix_timestamp;
+
+    emit!(PositionOpened {
+        fund: position.fund,
+        investor: position.investor,
+        payer: ctx.accounts.payer.key(),
+        timestamp: position.last_activity_at,
+    });
+    Ok(())
+}
@@ -1,8 +1,10 @@
+use std::ops::DerefMut;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer};
+use anchor_spl::token_interface::{self, TokenInterface, TokenAccount, Mint, TransferChecked};
 // Removed unused CloseAccount, InitializeAccount, spl_token, and Pack import
 use crate::state::*;
 use crate::errors::*;
+use crate::events::*;
 
 #[derive(Accounts)]
 pub struct PayFundInvestors<'info> {
@@ -34,56 +36,66 @@ pub struct PayFundInvestors<'info> {
         token::mint = fund.base_mint,
         token::authority = fund
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
 
     // Move system program earlier to match on-chain account order expectations
     pub system_program: Program<'info, System>,
 
     /// Base mint (must be NATIVE_MINT for WSOL unwrapping path)
-    pub base_mint: Account<'info, Mint>,
+    #[account(address = fund.base_mint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
 
     /// Temporary WSOL token account PDA (created and closed within this ix)
     /// CHECK: PDA created by this instruction, owned by token program, closed by end
     #[account(mut)]
     pub temp_wsol_account: AccountInfo<'info>,
 
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     /// Treasury wallet for platform fees
-    #[account(mut)]
-    /// CHECK: Platform treasury receives SOL
+    #[account(mut, address = protocol_config.treasury @ FundError::InvalidTreasury)]
+    /// CHECK: Platform treasury receives SOL; must match the protocol config
     pub treasury: AccountInfo<'info>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(
+        mut,
+        seeds = [b"nav_history", fund.key().as_ref()],
+        bump
+    )]
+    pub nav_history: AccountLoader<'info, NavHistory>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 /// Distribute `total_amount` SOL from the vault to a batch of investors by share percentage.
 /// Remaining accounts must come in pairs per investor: [InvestorPosition, Investor System Account].
+/// Positions must be writable: their high-water marks are updated as performance fees crystallize.
 pub fn pay_fund_investors<'info>(
     ctx: Context<'_, '_, 'info, 'info, PayFundInvestors<'info>>,
     total_amount: u64,
 ) -> Result<()> {
     require!(total_amount > 0, FundError::InvalidAmount);
 
+    // Settle the management fee before value leaves the fund
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.fund.accrue_management_fee(now)?;
+
     let fund = &ctx.accounts.fund;
-    let perf_bps = fund.performance_fee; // 0..=5000 (0-50%)
-    require!(perf_bps <= 5000, FundError::InvalidFee);
+    let perf_bps = fund.performance_fee; // 0..=MAX_PERFORMANCE_FEE_BPS
+    require!(perf_bps <= Fund::MAX_PERFORMANCE_FEE_BPS, FundError::InvalidFee);
 
-    // Fees
-    let base_fee = total_amount / 100; // 1%
+    // Fees (performance fee is settled per recipient against their high-water mark below)
+    let config = &ctx.accounts.protocol_config;
+    let base_fee = config.platform_fee(total_amount);
     let after_base = total_amount
         .checked_sub(base_fee)
         .ok_or(FundError::MathOverflow)?;
-    let performance_fee = ((after_base as u128)
-        .checked_mul(perf_bps as u128)
-        .ok_or(FundError::MathOverflow)?
-        / 10_000) as u64;
-    let treasury_perf_share = performance_fee / 5; // 20%
-    let manager_perf_share = performance_fee
-        .checked_sub(treasury_perf_share)
-        .ok_or(FundError::MathOverflow)?; // 80%
-    let investor_pool = after_base
-        .checked_sub(performance_fee)
-        .ok_or(FundError::MathOverflow)?;
+    let share_price = fund.share_price()?;
 
     // Prepare signer seeds for vault transfers
     let fund_key = fund.key();
@@ -117,11 +129,36 @@ pub fn pay_fund_investors<'info>(
             let (recipient_slice, fee_atas) = ctx.remaining_accounts.split_at(fee_atas_split);
             require!(recipient_slice.len() % 3 == 0, FundError::InvalidInput);
 
-            // Calculate fees and investor distribution amounts (already computed above)
+            // Collect recipients and settle per-investor performance fees
+            let mut positions: Vec<Account<InvestorPosition>> = Vec::with_capacity(recipient_slice.len() / 3);
+            let mut atas: Vec<&AccountInfo> = Vec::with_capacity(recipient_slice.len() / 3);
+            for triple in recipient_slice.chunks(3) {
+                let pos: Account<InvestorPosition> = Account::try_from(&triple[0])?;
+                require!(pos.fund == fund.key(), FundError::InvalidInput);
+                require!(pos.investor == triple[1].key(), FundError::InvalidInput);
+                positions.push(pos);
+                atas.push(&triple[2]);
+            }
+            let (payouts, performance_fee) = settle_payouts(&mut positions, after_base, share_price, perf_bps)?;
+            for pos in &positions {
+                pos.exit(&crate::ID)?;
+            }
+            let treasury_perf_share = config.treasury_performance_cut(performance_fee);
+            let manager_perf_share = performance_fee
+                .checked_sub(treasury_perf_share)
+                .ok_or(FundError::MathOverflow)?;
             let treasury_total = base_fee
                 .checked_add(treasury_perf_share)
                 .ok_or(FundError::MathOverflow)?;
 
+            // Fee ATAs must belong to the configured treasury and the manager
+            let treasury_ata: InterfaceAccount<TokenAccount> = InterfaceAccount::try_from(&fee_atas[0])?;
+            require_keys_eq!(treasury_ata.owner, config.treasury, FundError::InvalidTreasury);
+            require_keys_eq!(treasury_ata.mint, fund.base_mint, FundError::InvalidMint);
+            let manager_ata: InterfaceAccount<TokenAccount> = InterfaceAccount::try_from(&fee_atas[1])?;
+            require_keys_eq!(manager_ata.owner, fund.manager, FundError::InvalidInput);
+            require_keys_eq!(manager_ata.mint, fund.base_mint, FundError::InvalidMint);
+
             // Build fund authority signer seeds
             let fund_manager_key = ctx.accounts.fund.manager;
             let fund_name_bytes = ctx.accounts.fund.name.as_bytes();
@@ -139,71 +176,52 @@ pub fn pay_fund_investors<'info>(
             if treasury_total > 0 {
                 let cpi_ctx = CpiContext::new_with_signer(
                     ctx.accounts.token_program.to_account_info(),
-                    Transfer {
+                    TransferChecked {
                         from: ctx.accounts.vault.to_account_info(),
+                        mint: ctx.accounts.base_mint.to_account_info(),
                         to: fee_atas[0].clone(), // Treasury WSOL ATA
                         authority: ctx.accounts.fund.to_account_info(),
                     },
                     &fund_signer_arr,
                 );
-                token::transfer(cpi_ctx, treasury_total)?;
+                token_interface::transfer_checked(cpi_ctx, treasury_total, ctx.accounts.base_mint.decimals)?;
             }
 
             // 2) Pay manager performance share in WSOL
             if manager_perf_share > 0 {
                 let cpi_ctx = CpiContext::new_with_signer(
                     ctx.accounts.token_program.to_account_info(),
-                    Transfer {
+                    TransferChecked {
                         from: ctx.accounts.vault.to_account_info(),
+                        mint: ctx.accounts.base_mint.to_account_info(),
                         to: fee_atas[1].clone(), // Manager WSOL ATA
                         authority: ctx.accounts.fund.to_account_info(),
                     },
                     &fund_signer_arr,
                 );
-                token::transfer(cpi_ctx, manager_perf_share)?;
-            }
-
-            // 3) Distribute investor_pool pro-rata to WSOL ATAs
-            let mut batch_total_shares: u128 = 0;
-            let mut recipients: Vec<(&AccountInfo, u64, &AccountInfo)> = Vec::with_capacity(recipient_slice.len() / 3);
-            for triple in recipient_slice.chunks(3) {
-                let pos_ai = &triple[0];
-                let inv_ai = &triple[1];
-                let ata_ai = &triple[2];
-                let pos: Account<InvestorPosition> = Account::try_from(pos_ai)?;
-                require!(pos.fund == fund.key(), FundError::InvalidInput);
-                require!(pos.investor == inv_ai.key(), FundError::InvalidInput);
-                batch_total_shares = batch_total_shares
-                    .checked_add(pos.shares as u128)
-                    .ok_or(FundError::MathOverflow)?;
-                recipients.push((inv_ai, pos.shares, ata_ai));
+                token_interface::transfer_checked(cpi_ctx, manager_perf_share, ctx.accounts.base_mint.decimals)?;
             }
-            require!(batch_total_shares > 0, FundError::InvalidShares);
-
-            let mut distributed: u64 = 0;
-            for (i, (_investor_ai, shares, ata_ai)) in recipients.iter().enumerate() {
-                let share_amount = if i == recipients.len() - 1 {
-                    investor_pool.checked_sub(distributed).ok_or(FundError::MathOverflow)?
-                } else {
-                    (((investor_pool as u128)
-                        .checked_mul(*shares as u128)
-                        .ok_or(FundError::MathOverflow)?)
-                        .checked_div(batch_total_shares)
-                        .ok_or(FundError::MathOverflow)?) as u64
-                };
 
+            // 3) Distribute net payouts to WSOL ATAs
+            for ((pos, ata_ai), share_amount) in positions.iter().zip(atas.iter()).zip(payouts.iter().copied()) {
                 if share_amount > 0 {
                     let cpi_ctx = CpiContext::new_with_signer(
                         ctx.accounts.token_program.to_account_info(),
-                        Transfer {
+                        TransferChecked {
                             from: ctx.accounts.vault.to_account_info(),
+                            mint: ctx.accounts.base_mint.to_account_info(),
                             to: (*ata_ai).clone(),
                             authority: ctx.accounts.fund.to_account_info(),
                         },
                         &fund_signer_arr,
                     );
-                    token::transfer(cpi_ctx, share_amount)?;
-                    distributed = distributed.checked_add(share_amount).ok_or(FundError::MathOverflow)?;
+                    token_interface::transfer_checked(cpi_ctx, share_amount, ctx.accounts.base_mint.decimals)?;
+                    emit!(InvestorPaid {
+                        fund: fund_key,
+                        investor: pos.investor,
+                        amount: share_amount,
+                        timestamp: now,
+                    });
                 }
             }
 
@@ -212,33 +230,43 @@ pub fn pay_fund_investors<'info>(
             // Avoid underflow: if accounting is behind actual lamports, saturate at zero
             fund_mut.total_assets = fund_mut.total_assets.saturating_sub(total_amount);
 
+            ctx.accounts.nav_history.load_mut()?.record(&ctx.accounts.fund, now)?;
+            emit!(PayoutDistributed {
+                fund: fund_key,
+                total_amount,
+                platform_fee: base_fee,
+                treasury_performance_fee: treasury_perf_share,
+                manager_performance_fee: manager_perf_share,
+                recipients: positions.len() as u16,
+                timestamp: now,
+            });
             return Ok(());
     }
 
     // SOL path (vault SOL balance is sufficient)
 
-    // Compute total shares for batch distribution from pairs (SOL path)
+    // Collect recipients from pairs and settle per-investor performance fees (SOL path)
     require!(ctx.remaining_accounts.len() % 2 == 0, FundError::InvalidInput);
-    let mut batch_total_shares: u128 = 0;
-    let mut recipients: Vec<(&AccountInfo, u64)> = Vec::with_capacity(ctx.remaining_accounts.len() / 2);
+    let mut positions: Vec<Account<InvestorPosition>> = Vec::with_capacity(ctx.remaining_accounts.len() / 2);
+    let mut investors: Vec<&AccountInfo> = Vec::with_capacity(ctx.remaining_accounts.len() / 2);
     for pair in ctx.remaining_accounts.chunks(2) {
-        let pos_ai = &pair[0];
-        let inv_ai = &pair[1];
-        let pos: Account<InvestorPosition> = Account::try_from(pos_ai)?;
+        let pos: Account<InvestorPosition> = Account::try_from(&pair[0])?;
         // Sanity checks
         require!(pos.fund == fund.key(), FundError::InvalidInput);
-        require!(pos.investor == inv_ai.key(), FundError::InvalidInput);
-
-        batch_total_shares = batch_total_shares
-            .checked_add(pos.shares as u128)
-            .ok_or(FundError::MathOverflow)?;
-        recipients.push((inv_ai, pos.shares));
+        require!(pos.investor == pair[1].key(), FundError::InvalidInput);
+        positions.push(pos);
+        investors.push(&pair[1]);
     }
-    require!(batch_total_shares > 0, FundError::InvalidShares);
-
-    // Helper to perform SOL transfer from vault PDA using seeds (SOL path)
+    let (payouts, performance_fee) = settle_payouts(&mut positions, after_base, share_price, perf_bps)?;
+    for pos in &positions {
+        pos.exit(&crate::ID)?;
+    }
+    let treasury_perf_share = config.treasury_performance_cut(performance_fee);
+    let manager_perf_share = performance_fee
+        .checked_sub(treasury_perf_share)
+        .ok_or(FundError::MathOverflow)?;
 
-    // 1) Pay treasury: base fee + 20% of performance fee
+    // 1) Pay treasury: platform fee + its share of the performance fee
     let treasury_total = base_fee
         .checked_add(treasury_perf_share)
         .ok_or(FundError::MathOverflow)?;
@@ -271,21 +299,8 @@ pub fn pay_fund_investors<'info>(
         anchor_lang::system_program::transfer(cpi_ctx, manager_perf_share)?;
     }
 
-    // 3) Distribute investor_pool pro-rata across provided positions
-    let mut distributed: u64 = 0;
-    for (i, (investor_ai, shares)) in recipients.iter().enumerate() {
-        let share_amount = if i == recipients.len() - 1 {
-            // give remainder to last recipient to ensure exact sum
-            investor_pool.checked_sub(distributed).ok_or(FundError::MathOverflow)?
-        } else {
-            (((investor_pool as u128)
-                .checked_mul(*shares as u128)
-                .ok_or(FundError::MathOverflow)?
-                )
-                .checked_div(batch_total_shares)
-                .ok_or(FundError::MathOverflow)?) as u64
-        };
-
+    // 3) Distribute net payouts across provided positions
+    for (investor_ai, share_amount) in investors.iter().zip(payouts.iter().copied()) {
         if share_amount > 0 {
             let cpi_accounts = anchor_lang::system_program::Transfer {
                 from: ctx.accounts.vault_sol_account.to_account_info(),
@@ -298,7 +313,12 @@ pub fn pay_fund_investors<'info>(
                 signer,
             );
             anchor_lang::system_program::transfer(cpi_ctx, share_amount)?;
-            distributed = distributed.checked_add(share_amount).ok_or(FundError::MathOverflow)?;
+            emit!(InvestorPaid {
+                fund: fund_key,
+                investor: investor_ai.key(),
+                amount: share_amount,
+                timestamp: now,
+            });
         }
     }
 
@@ -307,5 +327,112 @@ pub fn pay_fund_investors<'info>(
     // Avoid underflow: if accounting is behind actual lamports, saturate at zero
     fund_mut.total_assets = fund_mut.total_assets.saturating_sub(total_amount);
 
+    ctx.accounts.nav_history.load_mut()?.record(&ctx.accounts.fund, now)?;
+    emit!(PayoutDistributed {
+        fund: fund_key,
+        total_amount,
+        platform_fee: base_fee,
+        treasury_performance_fee: treasury_perf_share,
+        manager_performance_fee: manager_perf_share,
+        recipients: positions.len() as u16,
+        timestamp: now,
+    });
     Ok(())
 }
+
+/// Split `pool` pro-rata by shares across `positions` and charge the performance fee on each slice
+/// only where the position's value sits above its high-water mark. The part of a payout that was not
+/// charged is a return of capital, so the mark is lowered by it per share; gains already charged stay
+/// charged. Marks are updated in place; the caller writes the position accounts back.
+/// Returns the net amount owed to each recipient and the total performance fee.
+fn settle_payouts<P: DerefMut<Target = InvestorPosition>>(
+    positions: &mut [P],
+    pool: u64,
+    share_price: u64,
+    perf_bps: u16,
+) -> Result<(Vec<u64>, u64)> {
+    let batch_total_shares = positions
+        .iter()
+        .try_fold(0u128, |acc, p| acc.checked_add(p.shares as u128))
+        .ok_or(FundError::MathOverflow)?;
+    require!(batch_total_shares > 0, FundError::InvalidShares);
+
+    let mut payouts: Vec<u64> = Vec::with_capacity(positions.len());
+    let mut allocated: u64 = 0;
+    let mut performance_fee: u64 = 0;
+    let last = positions.len() - 1;
+    for (i, pos) in positions.iter_mut().enumerate() {
+        let gross = if i == last {
+            // give remainder to last recipient to ensure exact sum
+            pool.checked_sub(allocated).ok_or(FundError::MathOverflow)?
+        } else {
+            ((pool as u128)
+                .checked_mul(pos.shares as u128)
+                .ok_or(FundError::MathOverflow)?
+                / batch_total_shares) as u64
+        };
+        allocated = allocated.checked_add(gross).ok_or(FundError::MathOverflow)?;
+
+        let value = (pos.shares as u128 * share_price as u128 / SHARE_PRICE_SCALE) as u64;
+        let charged = pos.gain_above_mark(pos.shares, value).min(gross);
+        let fee = (charged as u128 * perf_bps as u128 / 10_000) as u64;
+        if pos.shares > 0 {
+            let capital_per_share = (gross - charged) as u128 * SHARE_PRICE_SCALE / pos.shares as u128;
+            pos.high_water_mark = pos.high_water_mark.saturating_sub(capital_per_share.min(u64::MAX as u128) as u64);
+        }
+        payouts.push(gross - fee);
+        performance_fee = performance_fee.checked_add(fee).ok_or(FundError::MathOverflow)?;
+    }
+    Ok((payouts, performance_fee))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::investor::tests::position;
+
+    const PRICE_2X: u64 = 2 * SHARE_PRICE_SCALE as u64;
+
+    #[test]
+    fn full_payout_charges_only_the_gain_and_returns_capital() {
+        let mut positions = vec![
+            Box::new(position(100, SHARE_PRICE_SCALE as u64)),
+            Box::new(position(100, PRICE_2X)),
+        ];
+        let (payouts, fee) = settle_payouts(&mut positions, 400, PRICE_2X, 2_000).unwrap();
+        assert_eq!(payouts, vec![180, 200]);
+        assert_eq!(fee, 20);
+        assert_eq!(positions[0].high_water_mark, 0);
+        assert_eq!(positions[1].high_water_mark, 0);
+    }
+
+    #[test]
+    fn partial_payout_lowers_the_mark_by_the_capital_returned() {
+        let mut positions = vec![
+            Box::new(position(100, SHARE_PRICE_SCALE as u64)),
+            Box::new(position(100, PRICE_2X)),
+        ];
+        let (payouts, fee) = settle_payouts(&mut positions, 100, PRICE_2X, 2_000).unwrap();
+        assert_eq!(payouts, vec![40, 50]);
+        assert_eq!(fee, 10);
+        // The first slice was all gain, the second all capital
+        assert_eq!(positions[0].high_water_mark, SHARE_PRICE_SCALE as u64);
+        assert_eq!(positions[1].high_water_mark, 1_500_000_000);
+    }
+
+    #[test]
+    fn remainder_goes_to_the_last_recipient() {
+        let mut positions: Vec<_> = (0..3).map(|_| Box::new(position(1, 0))).collect();
+        let (payouts, fee) = settle_payouts(&mut positions, 101, 0, 2_000).unwrap();
+        assert_eq!(payouts, vec![33, 33, 35]);
+        assert_eq!(fee, 0);
+    }
+
+    #[test]
+    fn rejects_a_batch_without_shares() {
+        let mut none: Vec<Box<InvestorPosition>> = Vec::new();
+        assert!(settle_payouts(&mut none, 100, PRICE_2X, 2_000).is_err());
+        let mut empty = vec![Box::new(position(0, 0))];
+        assert!(settle_payouts(&mut empty, 100, PRICE_2X, 2_000).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::state::Fund;
+use crate::events::*;
 
 #[derive(Accounts)]
 pub struct PdaLamportsTransfer<'info> {
@@ -37,6 +38,12 @@ pub fn pda_lamports_transfer(ctx: Context<PdaLamportsTransfer>, amount: u64) ->
         .checked_add(amount)
         .ok_or(ErrorCode::MathOverflow)?;
 
+    emit!(ManagerLamportTransfer {
+        fund: ctx.accounts.fund.key(),
+        to: to_info.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
     Ok(())
 }
 
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Approve as SplApprove, Revoke as SplRevoke, Token, TokenAccount};
+use anchor_spl::token_interface::{self, Approve as SplApprove, TokenInterface, TokenAccount};
 use crate::state::Fund;
+use crate::events::*;
 
 #[derive(Accounts)]
 pub struct PdaTokenApprove<'info> {
@@ -12,9 +13,9 @@ pub struct PdaTokenApprove<'info> {
     pub fund: Account<'info, Fund>,
 
     #[account(mut)]
-    pub source: Account<'info, TokenAccount>,
+    pub source: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     // Manager acts as the delegate; must be the configured fund.manager
     pub manager: Signer<'info>,
@@ -38,6 +39,14 @@ pub fn pda_token_approve(ctx: Context<PdaTokenApprove>, amount: u64) -> Result<(
         },
         signer_seeds,
     );
-    token::approve(cpi_ctx, amount)?;
+    token_interface::approve(cpi_ctx, amount)?;
+
+    emit!(FundTokenApproved {
+        fund: f.key(),
+        account: ctx.accounts.source.key(),
+        delegate: ctx.accounts.manager.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
     Ok(())
 }
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Revoke as SplRevoke, Token, TokenAccount};
+use anchor_spl::token_interface::{self, Revoke as SplRevoke, TokenInterface, TokenAccount};
 use crate::state::Fund;
+use crate::events::*;
 
 #[derive(Accounts)]
 pub struct PdaTokenRevoke<'info> {
@@ -12,9 +13,9 @@ pub struct PdaTokenRevoke<'info> {
     pub fund: Account<'info, Fund>,
 
     #[account(mut)]
-    pub source: Account<'info, TokenAccount>,
+    pub source: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     // Only fund manager may trigger revoke
     pub manager: Signer<'info>,
@@ -37,6 +38,12 @@ pub fn pda_token_revoke(ctx: Context<PdaTokenRevoke>) -> Result<()> {
         },
         signer_seeds,
     );
-    token::revoke(cpi_ctx)?;
+    token_interface::revoke(cpi_ctx)?;
+
+    emit!(FundTokenRevoked {
+        fund: f.key(),
+        account: ctx.accounts.source.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
     Ok(())
 }
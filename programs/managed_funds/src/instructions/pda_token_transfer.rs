@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer as SplTransfer};
+use anchor_spl::token_interface::{self, TokenInterface, TokenAccount, Mint, TransferChecked as SplTransfer};
 use crate::state::Fund;
+use crate::events::*;
 
 #[derive(Accounts)]
 pub struct PdaTokenTransfer<'info> {
@@ -12,12 +13,15 @@ pub struct PdaTokenTransfer<'info> {
     pub fund: Account<'info, Fund>,
 
     #[account(mut)]
-    pub from: Account<'info, TokenAccount>,
+    pub from: InterfaceAccount<'info, TokenAccount>,
 
     #[account(mut)]
-    pub to: Account<'info, TokenAccount>,
+    pub to: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(address = from.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 
     // Enforce only the fund manager can trigger transfers
     pub manager: Signer<'info>,
@@ -39,12 +43,22 @@ pub fn pda_token_transfer(ctx: Context<PdaTokenTransfer>, amount: u64) -> Result
         ctx.accounts.token_program.to_account_info(),
         SplTransfer {
             from: ctx.accounts.from.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.to.to_account_info(),
             authority: f.to_account_info(),
         },
         signer_seeds,
     );
 
-    token::transfer(cpi_ctx, amount)?;
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    emit!(ManagerTokenTransfer {
+        fund: f.key(),
+        from: ctx.accounts.from.key(),
+        to: ctx.accounts.to.key(),
+        mint: ctx.accounts.from.mint,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
     Ok(())
 }
@@ -0,0 +1,227 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::{self, TokenInterface, TokenAccount, Mint, Burn};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct RedeemInKind<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump,
+        constraint = fund.redemption_mode == RedemptionMode::Instant @ FundError::InvalidRedemptionMode
+    )]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"position", investor.key().as_ref(), fund.key().as_ref()],
+        bump,
+        has_one = investor,
+        has_one = fund
+    )]
+    pub investor_position: Account<'info, InvestorPosition>,
+
+    #[account(
+        mut,
+        seeds = [b"shares", fund.key().as_ref()],
+        bump = fund.shares_bump,
+        mint::authority = fund,
+        mint::token_program = shares_token_program
+    )]
+    pub shares_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = shares_mint,
+        token::authority = investor,
+        token::token_program = shares_token_program
+    )]
+    pub investor_shares_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", fund.key().as_ref()],
+        bump = fund.vault_bump,
+        token::mint = fund.base_mint,
+        token::authority = fund
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = fund.base_mint,
+        token::authority = investor
+    )]
+    pub investor_base_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"nav_history", fund.key().as_ref()],
+        bump
+    )]
+    pub nav_history: AccountLoader<'info, NavHistory>,
+
+    #[account(address = fund.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token program of the shares mint (Token-2022 for funds created with transferable shares)
+    pub shares_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Burn `shares` and hand the investor the same fraction of every fund holding: the base vault,
+/// every registered position and the Fund PDA lamports above its rent-exempt minimum.
+/// Only available in Instant redemption mode; Epoch funds redeem through the epoch queue and gate.
+/// The performance fee on gain above the investor's mark is withheld in shares and accrued to
+/// the manager through `pending_fee_shares`.
+/// Remaining accounts must list every registered position of the fund as quadruples:
+/// [VaultPosition (writable), mint, the position's canonical Fund token account, investor token account of that mint].
+/// Positions may live under either token program; the program owning each mint must be among the
+/// transaction accounts. With `Fund::MAX_POSITIONS` positions the transaction stays within the
+/// 64-account lock limit but needs an address lookup table to fit the packet size.
+pub fn redeem_in_kind<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RedeemInKind<'info>>,
+    shares: u64,
+) -> Result<()> {
+    require!(shares > 0, FundError::InvalidShares);
+    require!(
+        ctx.accounts.investor_position.shares >= shares,
+        FundError::InsufficientFunds
+    );
+    require!(
+        ctx.remaining_accounts.len() == ctx.accounts.fund.total_positions as usize * 4,
+        FundError::InvalidInput
+    );
+
+    let clock = Clock::get()?;
+    // Settle the management fee so the fraction is taken from the diluted share count
+    ctx.accounts.fund.accrue_management_fee(clock.unix_timestamp)?;
+
+    let fund = &ctx.accounts.fund;
+    let investor_position = &ctx.accounts.investor_position;
+
+    // Performance fee, converted to shares at the current NAV; those shares are not redeemed
+    let value = fund.calculate_withdrawal_amount(shares);
+    let profit = investor_position.gain_above_mark(shares, value);
+    let performance_fee = (profit as u128 * fund.performance_fee as u128 / 10000) as u64;
+    let fee_shares = if value == 0 {
+        0
+    } else {
+        (shares as u128 * performance_fee as u128 / value as u128) as u64
+    };
+    let redeemed = shares - fee_shares;
+    let redeemed_value = fund.calculate_withdrawal_amount(redeemed);
+    let total_shares = fund.total_shares as u128;
+    let portion = |amount: u64| (amount as u128 * redeemed as u128 / total_shares) as u64;
+
+    // Burn everything the investor gives up; fee shares are re-issued to the manager on collection
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.shares_token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.shares_mint.to_account_info(),
+            from: ctx.accounts.investor_shares_account.to_account_info(),
+            authority: ctx.accounts.investor.to_account_info(),
+        },
+    );
+    token_interface::burn(burn_ctx, shares)?;
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"fund", fund.manager.as_ref(), fund.name.as_bytes(), &[fund.bump]]];
+    // transfer_checked under whichever token program owns the mint
+    let transfer = |from: AccountInfo<'info>, mint: AccountInfo<'info>, to: AccountInfo<'info>, decimals: u8, amount: u64| -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let ix = spl_token_2022::instruction::transfer_checked(
+            mint.owner,
+            from.key,
+            mint.key,
+            to.key,
+            &fund.key(),
+            &[],
+            amount,
+            decimals,
+        )?;
+        invoke_signed(&ix, &[from, mint, to, fund.to_account_info()], signer_seeds)?;
+        Ok(())
+    };
+
+    // Base vault
+    transfer(
+        ctx.accounts.vault.to_account_info(),
+        ctx.accounts.base_mint.to_account_info(),
+        ctx.accounts.investor_base_account.to_account_info(),
+        ctx.accounts.base_mint.decimals,
+        portion(ctx.accounts.vault.amount),
+    )?;
+
+    // Registered positions
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(fund.total_positions as usize);
+    for quad in ctx.remaining_accounts.chunks(4) {
+        let mut position: Account<VaultPosition> = Account::try_from(&quad[0])?;
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"vault_position", fund.key().as_ref(), position.mint.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(quad[0].key(), expected, FundError::InvalidInput);
+        require!(!seen.contains(&position.mint), FundError::InvalidInput);
+        seen.push(position.mint);
+
+        let mint: InterfaceAccount<Mint> = InterfaceAccount::try_from(&quad[1])?;
+        require_keys_eq!(quad[1].key(), position.mint, FundError::InvalidMint);
+        position.require_holding(&quad[2].key())?;
+        let mut fund_account: InterfaceAccount<TokenAccount> = InterfaceAccount::try_from(&quad[2])?;
+        require_keys_eq!(fund_account.owner, fund.key(), FundError::InvalidInput);
+        require_keys_eq!(fund_account.mint, position.mint, FundError::InvalidMint);
+        let investor_account: InterfaceAccount<TokenAccount> = InterfaceAccount::try_from(&quad[3])?;
+        require_keys_eq!(investor_account.owner, ctx.accounts.investor.key(), FundError::InvalidInput);
+        require_keys_eq!(investor_account.mint, position.mint, FundError::InvalidMint);
+
+        transfer(quad[2].clone(), quad[1].clone(), quad[3].clone(), mint.decimals, portion(fund_account.amount))?;
+
+        fund_account.reload()?;
+        position.refresh(fund_account.amount, clock.unix_timestamp);
+        position.exit(ctx.program_id)?;
+    }
+
+    // Lamports held directly by the Fund PDA, above its rent-exempt minimum
+    let fund_ai = fund.to_account_info();
+    let rent_floor = Rent::get()?.minimum_balance(fund_ai.data_len());
+    let lamports_out = portion(fund_ai.lamports().saturating_sub(rent_floor));
+    if lamports_out > 0 {
+        **fund_ai.try_borrow_mut_lamports()? -= lamports_out;
+        **ctx.accounts.investor.to_account_info().try_borrow_mut_lamports()? += lamports_out;
+    }
+
+    let fund = &mut ctx.accounts.fund;
+    fund.total_shares = fund.total_shares.checked_sub(redeemed).ok_or(FundError::MathOverflow)?;
+    fund.total_assets = fund.total_assets.saturating_sub(redeemed_value);
+    fund.pending_fee_shares = fund.pending_fee_shares.checked_add(fee_shares).ok_or(FundError::MathOverflow)?;
+
+    let investor_position = &mut ctx.accounts.investor_position;
+    investor_position.shares = investor_position.shares.checked_sub(shares).ok_or(FundError::MathOverflow)?;
+    investor_position.total_withdrawn = investor_position.total_withdrawn.checked_add(redeemed_value).ok_or(FundError::MathOverflow)?;
+    investor_position.last_activity_at = clock.unix_timestamp;
+    if investor_position.shares == 0 {
+        investor_position.high_water_mark = 0;
+    }
+
+    ctx.accounts.nav_history.load_mut()?.record(&ctx.accounts.fund, clock.unix_timestamp)?;
+
+    emit!(RedeemedInKind {
+        fund: ctx.accounts.fund.key(),
+        investor: ctx.accounts.investor.key(),
+        shares_burned: shares,
+        fee_shares,
+        value: redeemed_value,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct RefreshNav<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump,
+        constraint = fund.nav_source == NavSource::Oracle @ FundError::InvalidOracle
+    )]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        seeds = [b"vault", fund.key().as_ref()],
+        bump = fund.vault_bump,
+        token::mint = fund.base_mint,
+        token::authority = fund
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"nav_history", fund.key().as_ref()],
+        bump
+    )]
+    pub nav_history: AccountLoader<'info, NavHistory>,
+}
+
+/// Recompute `total_assets` from the base vault plus every registered position valued at its
+/// oracle price. Permissionless; usually bundled right before deposit or epoch settlement.
+/// Remaining accounts must list every registered position of the fund as triples:
+/// [VaultPosition (writable), the position's canonical Fund token account, OracleFeed of that mint].
+pub fn refresh_nav<'info>(ctx: Context<'_, '_, 'info, 'info, RefreshNav<'info>>) -> Result<()> {
+    let fund = &ctx.accounts.fund;
+    require!(
+        ctx.remaining_accounts.len() == fund.total_positions as usize * 3,
+        FundError::InvalidInput
+    );
+    let now = Clock::get()?.unix_timestamp;
+
+    let mut nav = ctx.accounts.vault.amount as u128;
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(fund.total_positions as usize);
+    for triple in ctx.remaining_accounts.chunks(3) {
+        let mut position: Account<VaultPosition> = Account::try_from(&triple[0])?;
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"vault_position", fund.key().as_ref(), position.mint.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(triple[0].key(), expected, FundError::InvalidInput);
+        require!(!seen.contains(&position.mint), FundError::InvalidInput);
+        seen.push(position.mint);
+
+        position.require_holding(&triple[1].key())?;
+        let token_account: InterfaceAccount<TokenAccount> = InterfaceAccount::try_from(&triple[1])?;
+        require_keys_eq!(token_account.owner, fund.key(), FundError::InvalidInput);
+        require_keys_eq!(token_account.mint, position.mint, FundError::InvalidMint);
+
+        let feed: Account<OracleFeed> = Account::try_from(&triple[2])?;
+        let (expected_feed, _) = Pubkey::find_program_address(
+            &[b"oracle_feed", position.mint.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(triple[2].key(), expected_feed, FundError::InvalidOracle);
+        require_keys_eq!(feed.mint(), position.mint, FundError::InvalidOracle);
+
+        nav += feed.latest_price().value_of(token_account.amount, now)? as u128;
+
+        position.refresh(token_account.amount, now);
+        position.exit(ctx.program_id)?;
+    }
+
+    let nav = u64::try_from(nav).map_err(|_| error!(FundError::MathOverflow))?;
+    let fund = &mut ctx.accounts.fund;
+    // A move beyond the circuit breaker pauses deposits and leaves the previous NAV in place
+    let accepted = fund.check_nav_move(nav, now);
+    emit!(NavUpdated {
+        fund: fund.key(),
+        attestor: Pubkey::default(),
+        nav_value: nav,
+        accepted,
+        timestamp: now,
+    });
+    if !accepted {
+        crate::log!("refresh_nav: breaker tripped, deposits paused");
+        return Ok(());
+    }
+    fund.total_assets = nav;
+    fund.nav_updated_at = now;
+
+    ctx.accounts.nav_history.load_mut()?.record(&ctx.accounts.fund, now)?;
+    Ok(())
+}
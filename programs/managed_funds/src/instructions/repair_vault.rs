@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use anchor_spl::token_interface::{self, TokenInterface, Mint};
 use crate::state::Fund;
+use crate::events::*;
 
 #[derive(Accounts)]
 pub struct RepairVault<'info> {
@@ -25,11 +26,12 @@ pub struct RepairVault<'info> {
     /// CHECK: Will be created if missing; validated/initialized as TokenAccount in instruction
     pub vault: AccountInfo<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
     /// The base mint account (must match fund.base_mint)
-    pub base_mint: Account<'info, Mint>,
+    #[account(address = fund.base_mint, mint::token_program = token_program)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
 }
 
 pub fn repair_vault(ctx: Context<RepairVault>) -> Result<()> {
@@ -38,16 +40,25 @@ pub fn repair_vault(ctx: Context<RepairVault>) -> Result<()> {
         return Ok(());
     }
 
-    // Allocate the account at the PDA with the Token program as owner
+    // Allocate the account at the PDA with the base mint's token program as owner, sized for
+    // whatever account extensions that mint requires
+    let space = token_interface::get_account_data_size(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::GetAccountDataSize {
+                mint: ctx.accounts.base_mint.to_account_info(),
+            },
+        ),
+        &[],
+    )?;
     let rent = &ctx.accounts.rent;
-    let lamports = rent.minimum_balance(TokenAccount::LEN);
-    let space = TokenAccount::LEN as u64;
+    let lamports = rent.minimum_balance(space as usize);
 
     let fund_key = ctx.accounts.fund.key();
     let seeds: &[&[u8]] = &[b"vault", fund_key.as_ref(), &[ctx.accounts.fund.vault_bump]];
     let signer_seeds: &[&[&[u8]]] = &[seeds];
 
-    // Create the account owned by SPL Token program
+    // Create the account owned by the token program
     let ca = anchor_lang::system_program::CreateAccount {
         from: ctx.accounts.manager.to_account_info(),
         to: ctx.accounts.vault.to_account_info(),
@@ -57,18 +68,24 @@ pub fn repair_vault(ctx: Context<RepairVault>) -> Result<()> {
         ca,
         signer_seeds,
     );
-    anchor_lang::system_program::create_account(cpi_ctx, lamports, space, &token::ID)?;
+    anchor_lang::system_program::create_account(cpi_ctx, lamports, space, &ctx.accounts.token_program.key())?;
 
     // Initialize as TokenAccount for base_mint with owner = fund via CPI helper
     let cpi_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
-        anchor_spl::token::InitializeAccount3 {
+        token_interface::InitializeAccount3 {
             account: ctx.accounts.vault.to_account_info(),
             mint: ctx.accounts.base_mint.to_account_info(),
             authority: ctx.accounts.fund.to_account_info(),
         },
     );
-    token::initialize_account3(cpi_ctx)?;
+    token_interface::initialize_account3(cpi_ctx)?;
 
+    emit!(VaultRepaired {
+        fund: fund_key,
+        vault: ctx.accounts.vault.key(),
+        lamports,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
     Ok(())
 }
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SetNavAttestor<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump,
+        has_one = manager
+    )]
+    pub fund: Account<'info, Fund>,
+
+    pub manager: Signer<'info>,
+}
+
+/// Rotating the attestor invalidates attestations written by the previous key.
+pub fn set_nav_attestor(ctx: Context<SetNavAttestor>, attestor: Pubkey) -> Result<()> {
+    let fund = &mut ctx.accounts.fund;
+    let previous_attestor = fund.nav_attestor;
+    fund.nav_attestor = attestor;
+
+    emit!(NavAttestorSet {
+        fund: fund.key(),
+        previous_attestor,
+        attestor,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SetNavCircuitBreaker<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump,
+        has_one = manager
+    )]
+    pub fund: Account<'info, Fund>,
+
+    pub manager: Signer<'info>,
+}
+
+/// `max_nav_change_bps` of 0 disables the breaker.
+pub fn set_nav_circuit_breaker(
+    ctx: Context<SetNavCircuitBreaker>,
+    max_nav_change_bps: u16,
+    nav_window_secs: i64,
+) -> Result<()> {
+    require!(nav_window_secs > 0, FundError::InvalidInput);
+    let fund = &mut ctx.accounts.fund;
+    fund.max_nav_change_bps = max_nav_change_bps;
+    fund.nav_window_secs = nav_window_secs;
+    emit!(NavCircuitBreakerSet {
+        fund: fund.key(),
+        max_nav_change_bps,
+        nav_window_secs,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
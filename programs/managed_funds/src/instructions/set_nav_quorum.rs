@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SetNavQuorum<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump,
+        has_one = manager
+    )]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        init_if_needed,
+        payer = manager,
+        space = NavQuorum::SPACE,
+        seeds = [b"nav_quorum", fund.key().as_ref()],
+        bump
+    )]
+    pub nav_quorum: Account<'info, NavQuorum>,
+
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// A non-empty attestor list switches the fund to quorum pricing; an empty list disables the
+/// quorum and returns a quorum-priced fund to its single attestor. Pricing needs a fresh
+/// attestation from every registered attestor.
+pub fn set_nav_quorum(
+    ctx: Context<SetNavQuorum>,
+    attestors: Vec<Pubkey>,
+    threshold: u8,
+    tolerance_bps: u16,
+) -> Result<()> {
+    require!(attestors.len() <= NavQuorum::MAX_ATTESTORS, FundError::InvalidInput);
+    require!(tolerance_bps <= 10_000, FundError::InvalidInput);
+    for (i, attestor) in attestors.iter().enumerate() {
+        require!(*attestor != Pubkey::default(), FundError::InvalidAttestor);
+        require!(!attestors[..i].contains(attestor), FundError::InvalidAttestor);
+    }
+    if !attestors.is_empty() {
+        require!(
+            threshold > 0 && threshold as usize <= attestors.len(),
+            FundError::InvalidInput
+        );
+    }
+
+    let fund = &mut ctx.accounts.fund;
+    if !attestors.is_empty() {
+        fund.nav_source = NavSource::Quorum;
+    } else if fund.nav_source == NavSource::Quorum {
+        fund.nav_source = NavSource::Attestor;
+    }
+
+    let quorum = &mut ctx.accounts.nav_quorum;
+    quorum.fund = fund.key();
+    quorum.attestors = attestors;
+    quorum.threshold = threshold;
+    quorum.tolerance_bps = tolerance_bps;
+    quorum.bump = ctx.bumps.nav_quorum;
+    emit!(NavQuorumSet {
+        fund: quorum.fund,
+        attestors: quorum.attestors.clone(),
+        threshold,
+        tolerance_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SetOraclePricing<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump,
+        has_one = manager
+    )]
+    pub fund: Account<'info, Fund>,
+
+    pub manager: Signer<'info>,
+}
+
+/// Disabling oracle pricing returns the fund to its single attestor.
+pub fn set_oracle_pricing(ctx: Context<SetOraclePricing>, enabled: bool) -> Result<()> {
+    let fund = &mut ctx.accounts.fund;
+    if enabled {
+        fund.nav_source = NavSource::Oracle;
+    } else if fund.nav_source == NavSource::Oracle {
+        fund.nav_source = NavSource::Attestor;
+    }
+    emit!(OraclePricingSet {
+        fund: fund.key(),
+        enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, Mint};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SetRedemptionMode<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump,
+        has_one = manager,
+        has_one = base_mint
+    )]
+    pub fund: Account<'info, Fund>,
+
+    /// Currently open epoch; created on first switch to Epoch mode
+    #[account(
+        init_if_needed,
+        payer = manager,
+        space = RedemptionEpoch::SPACE,
+        seeds = [b"epoch", fund.key().as_ref(), &fund.current_epoch.to_le_bytes()],
+        bump
+    )]
+    pub redemption_epoch: Account<'info, RedemptionEpoch>,
+
+    /// Holds base assets set aside at settlement until investors claim them
+    #[account(
+        init_if_needed,
+        payer = manager,
+        seeds = [b"redemption_vault", fund.key().as_ref()],
+        bump,
+        token::mint = base_mint,
+        token::authority = fund
+    )]
+    pub redemption_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn set_redemption_mode(
+    ctx: Context<SetRedemptionMode>,
+    mode: RedemptionMode,
+    redemption_gate_bps: u16,
+    epoch_duration_secs: i64,
+) -> Result<()> {
+    require!(
+        redemption_gate_bps > 0 && redemption_gate_bps <= 10_000,
+        FundError::InvalidInput
+    );
+    require!(epoch_duration_secs >= 0, FundError::InvalidInput);
+
+    let fund_key = ctx.accounts.fund.key();
+    let fund = &mut ctx.accounts.fund;
+    let epoch = &mut ctx.accounts.redemption_epoch;
+
+    if epoch.fund == Pubkey::default() {
+        epoch.fund = fund_key;
+        epoch.index = fund.current_epoch;
+        epoch.opened_at = Clock::get()?.unix_timestamp;
+        epoch.status = EpochStatus::Open;
+        epoch.bump = ctx.bumps.redemption_epoch;
+    }
+
+    // Requests already queued must be settled before the fund leaves Epoch mode
+    if mode == RedemptionMode::Instant {
+        require!(epoch.shares_queued == 0, FundError::InvalidRedemptionMode);
+    }
+
+    fund.redemption_mode = mode;
+    fund.redemption_gate_bps = redemption_gate_bps;
+    fund.epoch_duration_secs = epoch_duration_secs;
+    emit!(RedemptionModeSet {
+        fund: fund_key,
+        mode: fund.redemption_mode.clone(),
+        redemption_gate_bps,
+        epoch_duration_secs,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
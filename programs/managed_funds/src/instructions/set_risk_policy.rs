@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SetRiskPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump,
+        has_one = manager
+    )]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        init_if_needed,
+        payer = manager,
+        space = RiskPolicy::SPACE,
+        seeds = [b"risk_policy", fund.key().as_ref()],
+        bump
+    )]
+    pub risk_policy: Account<'info, RiskPolicy>,
+
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Once a policy exists every manager swap is checked against it; zero limits disable a check.
+/// The first policy and any change that only tightens apply at once. A change that allows anything
+/// the current policy forbids is staged and takes effect after `RiskPolicy::LOOSEN_DELAY_SECS`;
+/// submitting again replaces the staged change and restarts the delay.
+pub fn set_risk_policy(
+    ctx: Context<SetRiskPolicy>,
+    mint_list_mode: MintListMode,
+    mints: Vec<Pubkey>,
+    max_position_bps: u16,
+    max_trade_bps: u16,
+    max_trades_per_day: u16,
+) -> Result<()> {
+    require!(mints.len() <= RiskPolicy::MAX_MINTS, FundError::InvalidInput);
+    require!(
+        max_position_bps <= 10_000 && max_trade_bps <= 10_000,
+        FundError::InvalidInput
+    );
+    let now = Clock::get()?.unix_timestamp;
+
+    let fund = &mut ctx.accounts.fund;
+    let is_new = !fund.has_risk_policy;
+    fund.has_risk_policy = true;
+
+    let policy = &mut ctx.accounts.risk_policy;
+    policy.fund = fund.key();
+    policy.bump = ctx.bumps.risk_policy;
+    policy.apply_due(now);
+
+    let mut update = RiskPolicyUpdate {
+        mint_list_mode,
+        mints,
+        max_position_bps,
+        max_trade_bps,
+        max_trades_per_day,
+        effective_at: now,
+    };
+    if is_new || policy.is_no_looser(&update) {
+        policy.apply(update.clone());
+    } else {
+        update.effective_at = now.checked_add(RiskPolicy::LOOSEN_DELAY_SECS).ok_or(FundError::MathOverflow)?;
+        policy.pending = Some(update.clone());
+    }
+
+    emit!(RiskPolicySet {
+        fund: policy.fund,
+        mint_list_mode: update.mint_list_mode,
+        mints: update.mints,
+        max_position_bps,
+        max_trade_bps,
+        max_trades_per_day,
+        effective_at: update.effective_at,
+        timestamp: now,
+    });
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SetSwapProgram<'info> {
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = authority @ FundError::Unauthorized
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SwapProgram::SPACE,
+        seeds = [b"swap_program", program.key().as_ref()],
+        bump
+    )]
+    pub swap_program: Account<'info, SwapProgram>,
+
+    /// CHECK: only its address is recorded; must be an executable program
+    #[account(constraint = program.executable @ FundError::InvalidSwapProgram)]
+    pub program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Approve a swap program for the fund swap instructions, change its adapter, or disable it.
+pub fn set_swap_program(ctx: Context<SetSwapProgram>, adapter: SwapAdapter, enabled: bool) -> Result<()> {
+    require_keys_neq!(ctx.accounts.program.key(), crate::ID, FundError::InvalidSwapProgram);
+
+    let entry = &mut ctx.accounts.swap_program;
+    entry.program_id = ctx.accounts.program.key();
+    entry.adapter = adapter;
+    entry.enabled = enabled;
+    entry.bump = ctx.bumps.swap_program;
+
+    emit!(SwapProgramSet {
+        program: entry.program_id,
+        adapter,
+        enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
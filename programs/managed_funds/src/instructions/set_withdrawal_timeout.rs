@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SetWithdrawalTimeout<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump,
+        has_one = manager
+    )]
+    pub fund: Account<'info, Fund>,
+
+    pub manager: Signer<'info>,
+}
+
+pub fn set_withdrawal_timeout(ctx: Context<SetWithdrawalTimeout>, timeout_secs: i64) -> Result<()> {
+    require!(timeout_secs >= Fund::MIN_WITHDRAWAL_TIMEOUT_SECS, FundError::InvalidInput);
+    ctx.accounts.fund.withdrawal_timeout_secs = timeout_secs;
+    emit!(WithdrawalTimeoutSet {
+        fund: ctx.accounts.fund.key(),
+        timeout_secs,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
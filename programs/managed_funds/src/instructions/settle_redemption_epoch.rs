@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, TokenInterface, TokenAccount, Mint, TransferChecked};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SettleRedemptionEpoch<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump,
+        constraint = fund.redemption_mode == RedemptionMode::Epoch @ FundError::InvalidRedemptionMode
+    )]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"epoch", fund.key().as_ref(), &fund.current_epoch.to_le_bytes()],
+        bump = redemption_epoch.bump,
+        constraint = redemption_epoch.status == EpochStatus::Open @ FundError::InvalidEpoch
+    )]
+    pub redemption_epoch: Account<'info, RedemptionEpoch>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = RedemptionEpoch::SPACE,
+        seeds = [b"epoch", fund.key().as_ref(), &(fund.current_epoch + 1).to_le_bytes()],
+        bump
+    )]
+    pub next_epoch: Account<'info, RedemptionEpoch>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", fund.key().as_ref()],
+        bump = fund.vault_bump,
+        token::mint = fund.base_mint,
+        token::authority = fund
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"redemption_vault", fund.key().as_ref()],
+        bump,
+        token::mint = fund.base_mint,
+        token::authority = fund
+    )]
+    pub redemption_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The manager may settle at any time; anyone else once the epoch duration has elapsed
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"nav_history", fund.key().as_ref()],
+        bump
+    )]
+    pub nav_history: AccountLoader<'info, NavHistory>,
+
+    #[account(address = fund.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Price every request queued in the open epoch at the current NAV, move the payout for the
+/// shares admitted by the redemption gate into the redemption vault and open the next epoch.
+/// The manager is expected to have liquidated enough into the base vault beforehand.
+/// Quorum funds must pass [NavQuorum, NavAttestation...] as remaining accounts, one attestation
+/// per registered attestor; settlement is priced at the quorum median, under the same floor and
+/// circuit breaker as a single attestation. Oracle funds must have run refresh_nav beforehand.
+pub fn settle_redemption_epoch(ctx: Context<SettleRedemptionEpoch>) -> Result<()> {
+    let clock = Clock::get()?;
+    let fund_key = ctx.accounts.fund.key();
+
+    {
+        let fund = &ctx.accounts.fund;
+        let epoch = &ctx.accounts.redemption_epoch;
+        let epoch_end = epoch.opened_at.saturating_add(fund.epoch_duration_secs);
+        require!(
+            ctx.accounts.payer.key() == fund.manager || clock.unix_timestamp >= epoch_end,
+            FundError::InvalidEpoch
+        );
+    }
+
+    ctx.accounts.fund.require_fresh_nav(clock.unix_timestamp)?;
+    if ctx.accounts.fund.nav_source == NavSource::Quorum {
+        let nav = NavQuorum::nav_from_accounts(
+            &fund_key,
+            ctx.remaining_accounts,
+            ctx.program_id,
+            clock.unix_timestamp,
+        )?;
+        let vault_amount = ctx.accounts.vault.amount;
+        if !ctx.accounts.fund.apply_quorum_nav(nav, vault_amount, clock.unix_timestamp) {
+            // Settling at the pre-breach NAV would misprice the epoch; leave it open and keep the pause
+            crate::log!("settle_redemption_epoch: breaker tripped, epoch left open");
+            return Ok(());
+        }
+    }
+
+    // Settle the management fee so redeemed shares are priced after dilution
+    ctx.accounts.fund.accrue_management_fee(clock.unix_timestamp)?;
+
+    let fund = &ctx.accounts.fund;
+    let queued = ctx.accounts.redemption_epoch.shares_queued;
+    let gate_shares = (fund.total_shares as u128 * fund.redemption_gate_bps as u128 / 10_000u128) as u64;
+    let shares_redeemed = queued.min(gate_shares);
+    let payout_total = fund.calculate_withdrawal_amount(shares_redeemed);
+    let share_price = fund.share_price()?;
+
+    require!(
+        ctx.accounts.vault.amount >= payout_total,
+        FundError::InsufficientFunds
+    );
+
+    if payout_total > 0 {
+        let fund_seeds = &[
+            b"fund",
+            fund.manager.as_ref(),
+            fund.name.as_bytes(),
+            &[fund.bump],
+        ];
+        let signer = &[&fund_seeds[..]];
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.vault.to_account_info(),
+                mint: ctx.accounts.base_mint.to_account_info(),
+                to: ctx.accounts.redemption_vault.to_account_info(),
+                authority: fund.to_account_info(),
+            },
+            signer,
+        );
+        token_interface::transfer_checked(transfer_ctx, payout_total, ctx.accounts.base_mint.decimals)?;
+    }
+
+    let fund = &mut ctx.accounts.fund;
+    fund.total_shares = fund.total_shares.checked_sub(shares_redeemed).ok_or(FundError::MathOverflow)?;
+    fund.total_assets = fund.total_assets.checked_sub(payout_total).ok_or(FundError::MathOverflow)?;
+
+    let epoch = &mut ctx.accounts.redemption_epoch;
+    epoch.shares_redeemed = shares_redeemed;
+    epoch.payout_total = payout_total;
+    epoch.share_price = share_price;
+    epoch.settled_at = clock.unix_timestamp;
+    epoch.status = EpochStatus::Settled;
+
+    fund.current_epoch = fund.current_epoch.checked_add(1).ok_or(FundError::MathOverflow)?;
+    let next_epoch = &mut ctx.accounts.next_epoch;
+    next_epoch.fund = fund_key;
+    next_epoch.index = fund.current_epoch;
+    next_epoch.opened_at = clock.unix_timestamp;
+    next_epoch.status = EpochStatus::Open;
+    next_epoch.bump = ctx.bumps.next_epoch;
+
+    ctx.accounts.nav_history.load_mut()?.record(&ctx.accounts.fund, clock.unix_timestamp)?;
+
+    emit!(RedemptionEpochSettled {
+        fund: fund_key,
+        epoch: ctx.accounts.redemption_epoch.index,
+        shares_queued: queued,
+        shares_redeemed,
+        payout_total,
+        share_price,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Account as Token2022Account;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+// Seed of the ExtraAccountMetaList PDA the token program reads before calling the hook
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+// Execute account order: 0 source, 1 mint, 2 destination, 3 owner, 4 extra metas list,
+// then the extra accounts below.
+const FUND_INDEX: u8 = 5;
+// Number of entries returned by share_transfer_extra_metas
+pub const SHARE_TRANSFER_EXTRA_METAS: usize = 4;
+
+/// Extra accounts resolved for every share transfer: the fund, the sender and receiver
+/// InvestorPositions and the sender's WithdrawalState PDA.
+pub fn share_transfer_extra_metas(fund: &Pubkey) -> Result<Vec<ExtraAccountMeta>> {
+    // Token account owner lives at bytes 32..64
+    let source_owner = Seed::AccountData { account_index: 0, data_index: 32, length: 32 };
+    let destination_owner = Seed::AccountData { account_index: 2, data_index: 32, length: 32 };
+    let fund_key = Seed::AccountKey { index: FUND_INDEX };
+    Ok(vec![
+        ExtraAccountMeta::new_with_pubkey(fund, false, false)?,
+        ExtraAccountMeta::new_with_seeds(
+            &[Seed::Literal { bytes: b"position".to_vec() }, source_owner.clone(), fund_key.clone()],
+            false,
+            true,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[Seed::Literal { bytes: b"position".to_vec() }, destination_owner, fund_key.clone()],
+            false,
+            true,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[Seed::Literal { bytes: b"withdrawal".to_vec() }, fund_key, source_owner],
+            false,
+            false,
+        )?,
+    ])
+}
+
+#[derive(Accounts)]
+pub struct ShareTransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: transfer authority (owner or delegate); validated by the token program
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: read by the token program to resolve the accounts below
+    #[account(seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()], bump)]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    #[account(constraint = fund.shares_mint == mint.key() @ FundError::InvalidMint)]
+    pub fund: Box<Account<'info, Fund>>,
+
+    #[account(
+        mut,
+        seeds = [b"position", source_token.owner.as_ref(), fund.key().as_ref()],
+        bump,
+        has_one = fund
+    )]
+    pub sender_position: Box<Account<'info, InvestorPosition>>,
+
+    /// Must exist before the transfer; see open_position, or use transfer_shares which opens it
+    #[account(
+        mut,
+        seeds = [b"position", destination_token.owner.as_ref(), fund.key().as_ref()],
+        bump,
+        has_one = fund
+    )]
+    pub receiver_position: Box<Account<'info, InvestorPosition>>,
+
+    /// CHECK: the sender's WithdrawalState PDA; only inspected when it exists
+    #[account(seeds = [b"withdrawal", fund.key().as_ref(), source_token.owner.as_ref()], bump)]
+    pub sender_withdrawal: UncheckedAccount<'info>,
+}
+
+/// Token-2022 transfer hook of the shares mint: move `amount` shares and the matching slice of
+/// cost basis from the sender's InvestorPosition to the receiver's. The receiver inherits the
+/// sender's high-water mark on those shares so transfers cannot reset the performance fee.
+/// Shares under an unfinished instant withdrawal cannot move. The receiver's position is not
+/// created here (the hook gets no payer), so a transfer to a wallet without one fails with
+/// AccountNotInitialized: wallets and other integrations must call open_position first.
+pub fn share_transfer_hook(ctx: Context<ShareTransferHook>, amount: u64) -> Result<()> {
+    // Only honour calls made by the token program in the middle of a transfer
+    {
+        let source_ai = ctx.accounts.source_token.to_account_info();
+        let data = source_ai.try_borrow_data()?;
+        let source = StateWithExtensions::<Token2022Account>::unpack(&data)?;
+        let hook = source.get_extension::<TransferHookAccount>()?;
+        require!(bool::from(hook.transferring), FundError::Unauthorized);
+    }
+
+    require_transferable(&ctx.accounts.sender_withdrawal.to_account_info(), ctx.program_id)?;
+
+    let sender_key = ctx.accounts.sender_position.key();
+    let receiver_key = ctx.accounts.receiver_position.key();
+    if amount == 0 || sender_key == receiver_key {
+        return Ok(());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let cost_basis = move_position_shares(
+        &mut ctx.accounts.sender_position,
+        &mut ctx.accounts.receiver_position,
+        amount,
+        now,
+    )?;
+
+    emit!(SharesTransferred {
+        fund: ctx.accounts.fund.key(),
+        from: ctx.accounts.sender_position.investor,
+        to: ctx.accounts.receiver_position.investor,
+        shares: amount,
+        cost_basis,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+/// Shares under an unfinished instant withdrawal of their owner cannot move; `withdrawal_ai` is
+/// the owner's WithdrawalState PDA and is only inspected when it exists.
+pub(crate) fn require_transferable(withdrawal_ai: &AccountInfo, program_id: &Pubkey) -> Result<()> {
+    if withdrawal_ai.owner == program_id && !withdrawal_ai.data_is_empty() {
+        let withdrawal = WithdrawalState::try_deserialize(&mut &withdrawal_ai.try_borrow_data()?[..])?;
+        require!(
+            matches!(withdrawal.status, WithdrawalStatus::Queued | WithdrawalStatus::Completed | WithdrawalStatus::Failed),
+            FundError::InvalidWithdrawalStatus
+        );
+    }
+    Ok(())
+}
+
+/// Move `amount` shares and the matching slice of cost basis between two positions; the receiver
+/// inherits the sender's mark on them. Returns the cost basis moved.
+pub(crate) fn move_position_shares(
+    sender: &mut InvestorPosition,
+    receiver: &mut InvestorPosition,
+    amount: u64,
+    now: i64,
+) -> Result<u64> {
+    require!(sender.shares >= amount, FundError::InsufficientFunds);
+    let net_invested = sender.total_deposited.saturating_sub(sender.total_withdrawn);
+    let cost_basis = (net_invested as u128 * amount as u128 / sender.shares as u128) as u64;
+    let mark = sender.high_water_mark;
+    sender.shares -= amount;
+    sender.total_deposited -= cost_basis;
+    sender.last_activity_at = now;
+    if sender.shares == 0 {
+        sender.high_water_mark = 0;
+    }
+
+    receiver.record_entry(amount, mark)?;
+    receiver.shares = receiver.shares.checked_add(amount).ok_or(FundError::MathOverflow)?;
+    receiver.total_deposited = receiver.total_deposited.checked_add(cost_basis).ok_or(FundError::MathOverflow)?;
+    if receiver.first_deposit_at == 0 {
+        receiver.first_deposit_at = now;
+        receiver.initial_investment = cost_basis;
+    }
+    receiver.last_activity_at = now;
+    Ok(cost_basis)
+}
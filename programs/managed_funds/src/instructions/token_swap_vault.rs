@@ -1,16 +1,16 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{instruction::Instruction, program::invoke_signed};
-use anchor_spl::token::Token;
-use crate::state::Fund;
-use std::str::FromStr;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, Mint};
+use crate::state::{Fund, VaultPosition, Trade, RiskPolicy, OracleFeed, OraclePrice, PriceSource, SwapProgram};
+use crate::errors::FundError;
+use crate::events::*;
+use crate::mint_extensions::require_supported_mint;
 
-// Standalone vault-based CPI to Jupiter, mirroring the provided example as closely as possible.
+// Standalone vault-based CPI to a registered swap program (Jupiter or a direct DEX adapter).
 
 pub const VAULT_SOL_SEED: &[u8] = b"vault_sol"; // retained for other flows; not used as signer here
 
-// Jupiter Aggregator v6 program id
-pub const JUPITER_PROGRAM_ID: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
-
 #[derive(Accounts)]
 pub struct InitializeVault<'info> {
     #[account(mut)]
@@ -32,11 +32,84 @@ pub struct TokenSwapVault<'info> {
     pub fund: Account<'info, Fund>,
 
     // Require the fund manager to sign any swap initiated through this instruction
+    // (also pays rent when a new output mint is registered)
+    #[account(mut)]
     pub manager: Signer<'info>,
 
-    /// CHECK: Jupiter router program id; validated at runtime
-    pub jupiter_program: UncheckedAccount<'info>,
-    pub token_program: Program<'info, Token>,
+    /// Mint spent by the swap
+    pub input_mint: InterfaceAccount<'info, Mint>,
+
+    /// Fund-owned token account the swap input is drawn from
+    #[account(
+        token::mint = input_mint,
+        token::authority = fund
+    )]
+    pub fund_input_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Mint produced by the swap
+    pub output_mint: InterfaceAccount<'info, Mint>,
+
+    /// Fund-owned token account receiving the swap output; for a non-base mint this must be the
+    /// fund's ATA, which becomes the position's canonical holding account
+    #[account(
+        token::mint = output_mint,
+        token::authority = fund
+    )]
+    pub fund_output_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Registry entry for the output mint; required unless the output is the fund's base mint
+    #[account(
+        init_if_needed,
+        payer = manager,
+        space = VaultPosition::SPACE,
+        seeds = [b"vault_position", fund.key().as_ref(), output_mint.key().as_ref()],
+        bump
+    )]
+    pub output_position: Option<Account<'info, VaultPosition>>,
+
+    /// Journal entry for this swap
+    #[account(
+        init,
+        payer = manager,
+        space = Trade::SPACE,
+        seeds = [b"trade", fund.key().as_ref(), &fund.trade_count.to_le_bytes()],
+        bump
+    )]
+    pub trade: Box<Account<'info, Trade>>,
+
+    /// Fund risk policy; required once the fund has one
+    #[account(
+        mut,
+        seeds = [b"risk_policy", fund.key().as_ref()],
+        bump = risk_policy.bump
+    )]
+    pub risk_policy: Option<Box<Account<'info, RiskPolicy>>>,
+
+    /// Price feed of a non-base input mint, needed by the trade size limit
+    #[account(
+        seeds = [b"oracle_feed", input_mint.key().as_ref()],
+        bump = input_price_feed.bump
+    )]
+    pub input_price_feed: Option<Box<Account<'info, OracleFeed>>>,
+
+    /// Price feed of a non-base output mint, needed by the concentration limit
+    #[account(
+        seeds = [b"oracle_feed", output_mint.key().as_ref()],
+        bump = output_price_feed.bump
+    )]
+    pub output_price_feed: Option<Box<Account<'info, OracleFeed>>>,
+
+    /// Registry entry approving the router program
+    #[account(
+        seeds = [b"swap_program", router_program.key().as_ref()],
+        bump = swap_program.bump,
+        constraint = swap_program.enabled @ FundError::InvalidSwapProgram
+    )]
+    pub swap_program: Box<Account<'info, SwapProgram>>,
+
+    /// CHECK: swap program invoked with remaining_accounts; approved through `swap_program`
+    pub router_program: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
@@ -48,41 +121,35 @@ pub fn initialize_vault(_ctx: Context<InitializeVault>) -> Result<()> {
     Ok(())
 }
 
-// Forward a Jupiter router instruction using remaining_accounts and vault PDA as the program authority signer.
+// Forward a registered swap program instruction using remaining_accounts and vault PDA as the program authority signer.
+// The swap may only convert between the declared mints: the input account may lose at most `in_amount`,
+// the output account must gain at least `min_out`, and no other fund-owned token account passed to the
+// router may end up with fewer tokens.
 pub fn token_swap_vault<'info>(
     ctx: Context<'_, '_, 'info, 'info, TokenSwapVault<'info>>,
     data: Vec<u8>,
-    _tmp: Vec<u8>, // kept for parity with example; logged or ignored
+    in_amount: u64,
+    min_out: u64,
 ) -> Result<()> {
     // Enforce that the provided signer is the Fund's manager
     require_keys_eq!(ctx.accounts.manager.key(), ctx.accounts.fund.manager);
-    // Optional: validate first 8 bytes correspond to a Jupiter router discriminator
-    // We skip strict validation to keep this generic pass-through per example.
 
-    // Ensure we are calling the expected Jupiter program id
-    let expected = Pubkey::from_str(JUPITER_PROGRAM_ID)
-        .map_err(|_| error!(anchor_lang::error::ErrorCode::ConstraintSeeds))?;
-    require_keys_eq!(ctx.accounts.jupiter_program.key(), expected);
+    require_keys_neq!(ctx.accounts.input_mint.key(), ctx.accounts.output_mint.key(), FundError::InvalidMint);
+    require!(in_amount > 0, FundError::InvalidAmount);
 
-    // Build AccountMeta list from remaining_accounts and mark the fund PDA (user) as is_signer
+    // The router must be a registered swap program and the forwarded accounts must fit its adapter
     let user_key = ctx.accounts.fund.key();
-    // Debug: ensure the user key is actually present in remaining_accounts in the expected Jupiter router list
-    let mut user_present = false;
-    let mut user_index: i32 = -1;
-    for (i, acc) in ctx.remaining_accounts.iter().enumerate() {
-        if acc.key == &user_key {
-            user_present = true;
-            user_index = i as i32;
-            break;
-        }
-    }
-    if !user_present {
-        // Log minimal info to help diagnose account ordering issues
-        msg!("token_swap_vault: user (fund) not in remaining_accounts; remaining len {}", ctx.remaining_accounts.len());
-    } else {
-        msg!("token_swap_vault: user found in remaining_accounts at index {}", user_index);
+    let adapter = ctx.accounts.swap_program.adapter;
+    require!(adapter.supports_input(&ctx.accounts.input_mint.key()), FundError::InvalidSwapProgram);
+    let output_in_lamports = adapter.pays_native_lamports(&ctx.accounts.output_mint.key());
+    let mut measured = vec![ctx.accounts.fund_input_account.key()];
+    if !output_in_lamports {
+        measured.push(ctx.accounts.fund_output_account.key());
     }
-    let mut metas: Vec<anchor_lang::solana_program::instruction::AccountMeta> = ctx
+    adapter.validate_accounts(ctx.remaining_accounts, &user_key, &measured)?;
+
+    // Build AccountMeta list from remaining_accounts and mark the fund PDA (user) as is_signer
+    let metas: Vec<anchor_lang::solana_program::instruction::AccountMeta> = ctx
         .remaining_accounts
         .iter()
         .map(|acc| anchor_lang::solana_program::instruction::AccountMeta {
@@ -102,14 +169,201 @@ pub fn token_swap_vault<'info>(
     let bump = f.bump;
     let signer_seeds: &[&[&[u8]]] = &[&[b"fund", f.manager.as_ref(), f.name.as_bytes(), &[bump]]];
 
+    // Balances before the router runs, to journal what it actually moved
+    let input_before = ctx.accounts.fund_input_account.amount;
+    let output_before = if output_in_lamports {
+        ctx.accounts.fund.to_account_info().lamports()
+    } else {
+        ctx.accounts.fund_output_account.amount
+    };
+    let declared = [ctx.accounts.fund_input_account.key(), ctx.accounts.fund_output_account.key()];
+    let others_before = fund_token_balances(ctx.remaining_accounts, &user_key, &declared)?;
+
     let ix = Instruction {
-        program_id: ctx.accounts.jupiter_program.key(),
-        accounts: metas.drain(..).collect(),
+        program_id: ctx.accounts.router_program.key(),
+        accounts: metas,
         data,
     };
 
     invoke_signed(&ix, &infos, signer_seeds)
         .map_err(|_| error!(anchor_lang::error::ErrorCode::ConstraintRaw))?;
 
+    // Journal the measured trade
+    let input_mint = ctx.accounts.input_mint.key();
+    let output_mint = ctx.accounts.output_mint.key();
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.fund_input_account.reload()?;
+    ctx.accounts.fund_output_account.reload()?;
+    let amount_in = input_before.saturating_sub(ctx.accounts.fund_input_account.amount);
+    let amount = ctx.accounts.fund_output_account.amount;
+    let output_after = if output_in_lamports {
+        ctx.accounts.fund.to_account_info().lamports()
+    } else {
+        amount
+    };
+    let amount_out = output_after.saturating_sub(output_before);
+    require!(amount_in <= in_amount, FundError::UnexpectedBalanceChange);
+    require!(amount_out >= min_out, FundError::SlippageExceeded);
+    let others_after = fund_token_balances(ctx.remaining_accounts, &user_key, &declared)?;
+    require_no_losses(&others_before, &others_after)?;
+
+    let sequence = ctx.accounts.fund.trade_count;
+    **ctx.accounts.trade = Trade {
+        fund: ctx.accounts.fund.key(),
+        trader: ctx.accounts.manager.key(),
+        trade_type: Trade::classify(&ctx.accounts.fund.base_mint, &input_mint, &output_mint),
+        input_mint,
+        output_mint,
+        amount_in,
+        amount_out,
+        timestamp: now,
+        sequence,
+        bump: ctx.bumps.trade,
+    };
+    ctx.accounts.fund.trade_count = sequence.checked_add(1).ok_or(FundError::MathOverflow)?;
+    emit!(VaultSwap {
+        fund: ctx.accounts.fund.key(),
+        sequence,
+        input_mint,
+        output_mint,
+        amount_in,
+        amount_out,
+        timestamp: now,
+    });
+
+    // Register or refresh the output mint in the fund's position registry; the position's
+    // canonical holding is the account the risk policy measures
+    if output_mint != ctx.accounts.fund.base_mint {
+        require_supported_mint(&ctx.accounts.output_mint.to_account_info())?;
+        let fund = &mut ctx.accounts.fund;
+        let position = ctx
+            .accounts
+            .output_position
+            .as_mut()
+            .ok_or(FundError::InvalidInput)?;
+        if position.vault == Pubkey::default() {
+            let holding = get_associated_token_address_with_program_id(
+                &fund.key(),
+                &output_mint,
+                ctx.accounts.output_mint.to_account_info().owner,
+            );
+            position.vault = fund.key();
+            position.mint = output_mint;
+            position.created_at = now;
+            position.bump = ctx.bumps.output_position.ok_or(FundError::InvalidInput)?;
+            position.token_account = holding;
+            require!(fund.total_positions < Fund::MAX_POSITIONS, FundError::InvalidInput);
+            fund.total_positions += 1;
+        }
+        position.require_holding(&ctx.accounts.fund_output_account.key())?;
+        position.refresh(amount, now);
+    }
+
+    if ctx.accounts.fund.has_risk_policy {
+        let input_price = ctx.accounts.input_price_feed.as_ref().map(|f| f.latest_price());
+        let output_price = ctx.accounts.output_price_feed.as_ref().map(|f| f.latest_price());
+        let policy = ctx
+            .accounts
+            .risk_policy
+            .as_mut()
+            .ok_or(FundError::RiskPolicyViolation)?;
+        check_risk_policy(
+            policy,
+            &ctx.accounts.fund,
+            (&input_mint, amount_in, input_price),
+            (&output_mint, amount, output_price),
+            now,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Balances of every distinct fund-owned SPL token account among `accounts`, skipping `exclude`.
+/// Called before and after the router CPI and compared with `require_no_losses`.
+pub(crate) fn fund_token_balances(
+    accounts: &[AccountInfo],
+    fund: &Pubkey,
+    exclude: &[Pubkey],
+) -> Result<Vec<(Pubkey, u64)>> {
+    let mut balances: Vec<(Pubkey, u64)> = Vec::new();
+    for acc in accounts {
+        if (acc.owner != &anchor_spl::token::ID && acc.owner != &anchor_spl::token_2022::ID)
+            || exclude.contains(acc.key)
+            || balances.iter().any(|(key, _)| key == acc.key)
+        {
+            continue;
+        }
+        let data = acc.try_borrow_data()?;
+        // Mints and multisigs share the owner but not the layout
+        let token_account = match TokenAccount::try_deserialize(&mut &data[..]) {
+            Ok(token_account) => token_account,
+            Err(_) => continue,
+        };
+        if token_account.owner == *fund {
+            balances.push((*acc.key, token_account.amount));
+        }
+    }
+    Ok(balances)
+}
+
+/// Post-swap policy checks: output mint list, daily trade count, trade size and resulting
+/// concentration in the output mint, both measured against the fund's recorded NAV, which must be
+/// fresh for oracle-priced funds. A staged policy change whose delay has passed is applied first.
+/// Each side is (mint, amount, price feed); the output amount is the position's canonical holding
+/// after the swap. Non-base sides need a feed when a value limit is set.
+fn check_risk_policy(
+    policy: &mut RiskPolicy,
+    fund: &Fund,
+    input: (&Pubkey, u64, Option<OraclePrice>),
+    output: (&Pubkey, u64, Option<OraclePrice>),
+    now: i64,
+) -> Result<()> {
+    let (input_mint, amount_in, input_price) = input;
+    let (output_mint, output_balance, output_price) = output;
+    policy.apply_due(now);
+    if policy.max_trade_bps > 0 || policy.max_position_bps > 0 {
+        fund.require_fresh_nav(now)?;
+    }
+    let nav = fund.total_assets;
+
+    require!(
+        *output_mint == fund.base_mint || policy.allows_mint(output_mint),
+        FundError::RiskPolicyViolation
+    );
+    policy.record_trade(now)?;
+
+    if policy.max_trade_bps > 0 {
+        let trade_value = if *input_mint == fund.base_mint {
+            amount_in
+        } else {
+            input_price.ok_or(FundError::InvalidOracle)?.value_of(amount_in, now)?
+        };
+        require!(
+            RiskPolicy::within(trade_value, nav, policy.max_trade_bps),
+            FundError::RiskPolicyViolation
+        );
+    }
+
+    if policy.max_position_bps > 0 && *output_mint != fund.base_mint {
+        let position_value = output_price.ok_or(FundError::InvalidOracle)?.value_of(output_balance, now)?;
+        require!(
+            RiskPolicy::within(position_value, nav, policy.max_position_bps),
+            FundError::RiskPolicyViolation
+        );
+    }
+    Ok(())
+}
+
+/// Fail if any account of the `before` snapshot holds fewer tokens in `after`; an account that
+/// no longer parses as a fund-owned token account (e.g. closed by the router) counts as emptied.
+pub(crate) fn require_no_losses(before: &[(Pubkey, u64)], after: &[(Pubkey, u64)]) -> Result<()> {
+    for (key, amount_before) in before {
+        let amount_after = after.iter().find(|(k, _)| k == key).map_or(0, |(_, amount)| *amount);
+        if amount_after < *amount_before {
+            msg!("fund account {} lost {}", key, amount_before - amount_after);
+            return err!(FundError::UnexpectedBalanceChange);
+        }
+    }
     Ok(())
 }
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::instruction::update as update_transfer_hook;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, TokenAccount, Mint, TransferChecked};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::instructions::share_transfer_hook::{move_position_shares, require_transferable};
+
+#[derive(Accounts)]
+pub struct TransferShares<'info> {
+    #[account(
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump
+    )]
+    pub fund: Box<Account<'info, Fund>>,
+
+    #[account(
+        mut,
+        seeds = [b"shares", fund.key().as_ref()],
+        bump = fund.shares_bump,
+        mint::authority = fund,
+        mint::token_program = token_program
+    )]
+    pub shares_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = shares_mint,
+        token::authority = sender,
+        token::token_program = token_program
+    )]
+    pub sender_shares_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"position", sender.key().as_ref(), fund.key().as_ref()],
+        bump,
+        has_one = fund
+    )]
+    pub sender_position: Box<Account<'info, InvestorPosition>>,
+
+    /// CHECK: the sender's WithdrawalState PDA; only inspected when it exists
+    #[account(seeds = [b"withdrawal", fund.key().as_ref(), sender.key().as_ref()], bump)]
+    pub sender_withdrawal: UncheckedAccount<'info>,
+
+    /// CHECK: any wallet receiving the shares
+    #[account(constraint = receiver.key() != sender.key() @ FundError::InvalidInput)]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = sender,
+        associated_token::mint = shares_mint,
+        associated_token::authority = receiver,
+        associated_token::token_program = token_program
+    )]
+    pub receiver_shares_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Opened here when the receiver has no position yet
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = InvestorPosition::SPACE,
+        seeds = [b"position", receiver.key().as_ref(), fund.key().as_ref()],
+        bump
+    )]
+    pub receiver_position: Box<Account<'info, InvestorPosition>>,
+
+    /// Owner of the shares; pays rent for whatever is opened for the receiver
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Move `amount` shares to `receiver`, opening the receiver's shares account and InvestorPosition
+/// on demand at the sender's expense. The runtime forbids the token program from re-entering this
+/// program, so the fund (the hook authority) clears the mint's transfer hook around its own
+/// transfer and applies the hook's position bookkeeping itself; the hook is restored before return.
+pub fn transfer_shares(ctx: Context<TransferShares>, amount: u64) -> Result<()> {
+    require!(amount > 0, FundError::InvalidShares);
+    require_transferable(&ctx.accounts.sender_withdrawal.to_account_info(), ctx.program_id)?;
+    let now = Clock::get()?.unix_timestamp;
+
+    let receiver_position = &mut ctx.accounts.receiver_position;
+    if receiver_position.investor == Pubkey::default() {
+        receiver_position.investor = ctx.accounts.receiver.key();
+        receiver_position.fund = ctx.accounts.fund.key();
+        receiver_position.last_activity_at = now;
+        emit!(PositionOpened {
+            fund: receiver_position.fund,
+            investor: receiver_position.investor,
+            payer: ctx.accounts.sender.key(),
+            timestamp: now,
+        });
+    }
+
+    let fund = &ctx.accounts.fund;
+    let signer_seeds: &[&[&[u8]]] = &[&[b"fund", fund.manager.as_ref(), fund.name.as_bytes(), &[fund.bump]]];
+    let set_hook = |program_id: Option<Pubkey>| -> Result<()> {
+        let ix = update_transfer_hook(
+            &ctx.accounts.token_program.key(),
+            &ctx.accounts.shares_mint.key(),
+            &fund.key(),
+            &[],
+            program_id,
+        )?;
+        invoke_signed(
+            &ix,
+            &[ctx.accounts.shares_mint.to_account_info(), fund.to_account_info()],
+            signer_seeds,
+        )?;
+        Ok(())
+    };
+
+    set_hook(None)?;
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.sender_shares_account.to_account_info(),
+            mint: ctx.accounts.shares_mint.to_account_info(),
+            to: ctx.accounts.receiver_shares_account.to_account_info(),
+            authority: ctx.accounts.sender.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(transfer_ctx, amount, ctx.accounts.shares_mint.decimals)?;
+    set_hook(Some(crate::ID))?;
+
+    let cost_basis = move_position_shares(
+        &mut ctx.accounts.sender_position,
+        &mut ctx.accounts.receiver_position,
+        amount,
+        now,
+    )?;
+
+    emit!(SharesTransferred {
+        fund: ctx.accounts.fund.key(),
+        from: ctx.accounts.sender.key(),
+        to: ctx.accounts.receiver.key(),
+        shares: amount,
+        cost_basis,
+        timestamp: now,
+    });
+    Ok(())
+}
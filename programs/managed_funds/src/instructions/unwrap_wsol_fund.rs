@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, CloseAccount, Token, TokenAccount};
+use anchor_spl::token_interface::{self, CloseAccount, TokenInterface, TokenAccount};
 use crate::state::Fund;
+use crate::events::*;
 
 #[derive(Accounts)]
 pub struct UnwrapWsolFund<'info> {
@@ -16,14 +17,14 @@ pub struct UnwrapWsolFund<'info> {
         token::mint = anchor_spl::token::spl_token::native_mint::id(),
         token::authority = fund
     )]
-    pub fund_wsol_ata: Account<'info, TokenAccount>,
+    pub fund_wsol_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// Destination for unwrapped SOL (Fund PDA lamports account)
     /// CHECK: destination is the Fund PDA system account
     #[account(mut)]
     pub destination: AccountInfo<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn unwrap_wsol_fund(ctx: Context<UnwrapWsolFund>) -> Result<()> {
@@ -42,5 +43,15 @@ pub fn unwrap_wsol_fund(ctx: Context<UnwrapWsolFund>) -> Result<()> {
         },
         signer_seeds,
     );
-    token::close_account(cpi_ctx)
+    let lamports = ctx.accounts.fund_wsol_ata.to_account_info().lamports();
+    token_interface::close_account(cpi_ctx)?;
+
+    emit!(WsolUnwrapped {
+        fund: f.key(),
+        account: ctx.accounts.fund_wsol_ata.key(),
+        destination: ctx.accounts.destination.key(),
+        lamports,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
 }
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct UpdateOracleFeed<'info> {
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = authority @ FundError::Unauthorized
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = OracleFeed::SPACE,
+        seeds = [b"oracle_feed", mint.key().as_ref()],
+        bump
+    )]
+    pub oracle_feed: Account<'info, OracleFeed>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn update_oracle_feed(ctx: Context<UpdateOracleFeed>, price: i64, conf: u64, expo: i32) -> Result<()> {
+    require!(price > 0 && expo.unsigned_abs() <= OraclePrice::MAX_ABS_EXPO as u32, FundError::InvalidOracle);
+
+    let feed = &mut ctx.accounts.oracle_feed;
+    feed.mint = ctx.accounts.mint.key();
+    feed.price = OraclePrice {
+        price,
+        conf,
+        expo,
+        publish_time: Clock::get()?.unix_timestamp,
+    };
+    feed.bump = ctx.bumps.oracle_feed;
+    emit!(OracleFeedUpdated {
+        mint: feed.mint,
+        price,
+        conf,
+        expo,
+        timestamp: feed.price.publish_time,
+    });
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = authority @ FundError::Unauthorized
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn update_protocol_config(
+    ctx: Context<UpdateProtocolConfig>,
+    new_authority: Pubkey,
+    treasury: Pubkey,
+    platform_fee_bps: u16,
+    treasury_performance_share_bps: u16,
+) -> Result<()> {
+    require!(
+        ProtocolConfig::is_valid_fees(platform_fee_bps, treasury_performance_share_bps),
+        FundError::InvalidFee
+    );
+    require_keys_neq!(new_authority, Pubkey::default(), FundError::Unauthorized);
+
+    let config = &mut ctx.accounts.protocol_config;
+    config.authority = new_authority;
+    config.treasury = treasury;
+    config.platform_fee_bps = platform_fee_bps;
+    config.treasury_performance_share_bps = treasury_performance_share_bps;
+
+    emit!(ProtocolConfigUpdated {
+        authority: new_authority,
+        treasury,
+        platform_fee_bps,
+        treasury_performance_share_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::{self, mpl_token_metadata, Metadata, MetadataAccount, UpdateMetadataAccountsV2};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct UpdateShareMetadataUri<'info> {
+    #[account(
+        seeds = [b"fund", fund.manager.as_ref(), fund.name.as_bytes()],
+        bump = fund.bump,
+        has_one = manager
+    )]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata::ID.as_ref(), fund.shares_mint.as_ref()],
+        bump,
+        seeds::program = metadata::ID
+    )]
+    pub share_metadata: Box<Account<'info, MetadataAccount>>,
+
+    pub manager: Signer<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+/// Point the shares mint metadata at a new URI; name and symbol are kept.
+pub fn update_share_metadata_uri(ctx: Context<UpdateShareMetadataUri>, uri: String) -> Result<()> {
+    require!(uri.len() <= mpl_token_metadata::MAX_URI_LENGTH, FundError::InvalidShareMetadata);
+
+    let fund = &ctx.accounts.fund;
+    let current = &ctx.accounts.share_metadata;
+    require!(current.update_authority == fund.key(), FundError::InvalidShareMetadata);
+
+    let bump = [fund.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"fund", fund.manager.as_ref(), fund.name.as_bytes(), &bump]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_metadata_program.to_account_info(),
+        UpdateMetadataAccountsV2 {
+            metadata: current.to_account_info(),
+            update_authority: fund.to_account_info(),
+        },
+        signer_seeds,
+    );
+    // Stored strings are padded with NUL bytes
+    let data = mpl_token_metadata::types::DataV2 {
+        name: current.name.trim_end_matches('\0').to_string(),
+        symbol: current.symbol.trim_end_matches('\0').to_string(),
+        uri,
+        seller_fee_basis_points: current.seller_fee_basis_points,
+        creators: current.creators.clone(),
+        collection: current.collection.clone(),
+        uses: current.uses.clone(),
+    };
+    metadata::update_metadata_accounts_v2(cpi_ctx, None, Some(data.clone()), None, None)?;
+
+    emit!(ShareMetadataUriUpdated {
+        fund: fund.key(),
+        metadata: current.key(),
+        uri: data.uri,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -1,11 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{instruction::Instruction, program::invoke_signed};
-use anchor_spl::token::Token;
+use anchor_spl::token_interface::{TokenInterface, TokenAccount};
 use crate::state::*;
-use std::str::FromStr;
-
-// Jupiter Aggregator v6 program id
-pub const JUPITER_PROGRAM_ID: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+use crate::errors::*;
+use crate::events::*;
+use crate::instructions::token_swap_vault::{fund_token_balances, require_no_losses};
 
 #[derive(Accounts)]
 pub struct WithdrawSwapInstruction<'info> {
@@ -22,13 +21,42 @@ pub struct WithdrawSwapInstruction<'info> {
         bump = withdrawal_state.bump,
         has_one = investor,
         constraint = withdrawal_state.vault == fund.key(),
-        constraint = withdrawal_state.status == WithdrawalStatus::Initiated || withdrawal_state.status == WithdrawalStatus::ReadyToFinalize
+        constraint = withdrawal_state.status.can_transition_to(&WithdrawalStatus::Liquidating) @ FundError::InvalidWithdrawalStatus
     )]
     pub withdrawal_state: Account<'info, WithdrawalState>,
 
-    /// CHECK: Jupiter router program id; validated at runtime
-    pub jupiter_program: UncheckedAccount<'info>,
-    pub token_program: Program<'info, Token>,
+    /// Fund-owned token account being liquidated by this leg
+    #[account(token::authority = fund)]
+    pub fund_source_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Fund-owned WSOL account receiving proceeds; when omitted, proceeds are measured on the Fund PDA lamports
+    #[account(
+        token::mint = anchor_spl::token::spl_token::native_mint::id(),
+        token::authority = fund
+    )]
+    pub fund_destination_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Liquidation progress for the source mint; opened on the first leg for that mint
+    #[account(
+        init_if_needed,
+        payer = investor,
+        space = WithdrawalMintProgress::SPACE,
+        seeds = [b"withdrawal_mint", withdrawal_state.key().as_ref(), fund_source_account.mint.as_ref()],
+        bump
+    )]
+    pub mint_progress: Account<'info, WithdrawalMintProgress>,
+
+    /// Registry entry approving the router program
+    #[account(
+        seeds = [b"swap_program", router_program.key().as_ref()],
+        bump = swap_program.bump,
+        constraint = swap_program.enabled @ FundError::InvalidSwapProgram
+    )]
+    pub swap_program: Box<Account<'info, SwapProgram>>,
+
+    /// CHECK: swap program invoked with remaining_accounts; approved through `swap_program`
+    pub router_program: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 
     // Payer of compute/fees, and the owner of the withdrawal
@@ -36,38 +64,65 @@ pub struct WithdrawSwapInstruction<'info> {
     pub investor: Signer<'info>,
 }
 
-// Forward a single Jupiter router instruction for investor-initiated withdrawal.
-// - Expects remaining_accounts to contain the exact account metas required by Jupiter,
+// Forward a single registered swap program instruction for investor-initiated withdrawal.
+// - Expects remaining_accounts to contain the exact account metas required by that program,
 //   including the Fund PDA as the `user` account. We will mark the Fund PDA as signer.
-// - Updates WithdrawalState.input_allowed_total_sum and input_liquidated_sum to enable k-scaling in finalize.
+// - Measures the fund's source and destination balances around the CPI and records the actual deltas
+//   in WithdrawalState.input_liquidated_sum / sol_accumulated to enable k-scaling in finalize.
+// - Every other fund-owned token account passed to the router is snapshotted too; the leg fails if any
+//   of them lost tokens, so a leg declared for one mint cannot sell another holding of the fund.
+// - Liquidation of each mint is capped by the allowance snapshotted at initiation, tracked in the
+//   [withdrawal, mint] WithdrawalMintProgress PDA across legs.
 pub fn withdraw_swap_instruction<'info>(
     ctx: Context<'_, '_, 'info, 'info, WithdrawSwapInstruction<'info>>,
     router_data: Vec<u8>,
     in_amount: u64,
     out_min_amount: u64,
 ) -> Result<()> {
-    // Validate Jupiter program id
-    let expected = Pubkey::from_str(JUPITER_PROGRAM_ID)
-        .map_err(|_| error!(anchor_lang::error::ErrorCode::ConstraintSeeds))?;
-    require_keys_eq!(ctx.accounts.jupiter_program.key(), expected);
+    // The forwarded accounts must fit the registered program's adapter
+    let adapter = ctx.accounts.swap_program.adapter;
+    require!(
+        adapter.supports_input(&ctx.accounts.fund_source_account.mint),
+        FundError::InvalidSwapProgram
+    );
+    let mut measured = vec![ctx.accounts.fund_source_account.key()];
+    if let Some(dest) = &ctx.accounts.fund_destination_account {
+        measured.push(dest.key());
+    }
+    adapter.validate_accounts(ctx.remaining_accounts, &ctx.accounts.fund.key(), &measured)?;
 
     // Verify investor is the owner of this withdrawal
     require_keys_eq!(ctx.accounts.investor.key(), ctx.accounts.withdrawal_state.investor);
 
-    // Update allowed sum on first call (idempotent pattern: only add if allowed is 0)
-    let ws = &mut ctx.accounts.withdrawal_state;
-    if ws.input_allowed_total_sum == 0 {
-        ws.input_allowed_total_sum = in_amount;
-    } else {
-        // Allow multiple legs to accumulate allowed total
-        ws.input_allowed_total_sum = ws
-            .input_allowed_total_sum
-            .saturating_add(in_amount);
+    // Open progress for this mint on its first leg, with the allowance snapshotted at initiation
+    let source_mint = ctx.accounts.fund_source_account.mint;
+    let progress = &mut ctx.accounts.mint_progress;
+    if progress.withdrawal == Pubkey::default() {
+        let amount_allowed = ctx
+            .accounts
+            .withdrawal_state
+            .allowance_for(&source_mint)
+            .ok_or(FundError::InvalidMint)?;
+        progress.withdrawal = ctx.accounts.withdrawal_state.key();
+        progress.mint = source_mint;
+        progress.amount_liquidated = 0;
+        progress.amount_allowed = amount_allowed;
+        progress.bump = ctx.bumps.mint_progress;
     }
 
-    // Prepare CPI to Jupiter, marking Fund PDA as signer within remaining_accounts
+    // The leg may only liquidate what is left of the investor's allowance for this mint
+    require!(in_amount > 0 && in_amount <= progress.remaining(), FundError::WithdrawalCapExceeded);
+    let source_before = ctx.accounts.fund_source_account.amount;
+    let dest_before = match &ctx.accounts.fund_destination_account {
+        Some(dest) => dest.amount,
+        None => ctx.accounts.fund.to_account_info().lamports(),
+    };
+    let declared = [ctx.accounts.fund_source_account.key()];
+    let others_before = fund_token_balances(ctx.remaining_accounts, &ctx.accounts.fund.key(), &declared)?;
+
+    // Prepare CPI to the swap program, marking Fund PDA as signer within remaining_accounts
     let user_key = ctx.accounts.fund.key();
-    let mut metas: Vec<anchor_lang::solana_program::instruction::AccountMeta> = ctx
+    let metas: Vec<anchor_lang::solana_program::instruction::AccountMeta> = ctx
         .remaining_accounts
         .iter()
         .map(|acc| anchor_lang::solana_program::instruction::AccountMeta {
@@ -88,19 +143,52 @@ pub fn withdraw_swap_instruction<'info>(
     let seeds: &[&[u8]] = &[b"fund", f.manager.as_ref(), f.name.as_bytes(), &[f.bump]];
     let signer_seeds: &[&[&[u8]]] = &[seeds];
 
-    // CPI into Jupiter router
-    let ix = Instruction { program_id: ctx.accounts.jupiter_program.key(), accounts: metas.drain(..).collect(), data: router_data };
+    // CPI into the swap program
+    let ix = Instruction { program_id: ctx.accounts.router_program.key(), accounts: metas, data: router_data };
     invoke_signed(&ix, &infos, signer_seeds)
         .map_err(|_| error!(anchor_lang::error::ErrorCode::ConstraintRaw))?;
 
-    // On success, conservatively account for progress:
-    // - Add input in_amount to liquidated input sum
-    // - Add a lower bound on SOL received using out_min_amount (based on quote threshold)
-    ws.input_liquidated_sum = ws.input_liquidated_sum.saturating_add(in_amount);
-    ws.sol_accumulated = ws.sol_accumulated.saturating_add(out_min_amount);
+    // Measure what the router actually moved
+    ctx.accounts.fund_source_account.reload()?;
+    let source_after = ctx.accounts.fund_source_account.amount;
+    let dest_after = match ctx.accounts.fund_destination_account.as_mut() {
+        Some(dest) => {
+            dest.reload()?;
+            dest.amount
+        }
+        None => ctx.accounts.fund.to_account_info().lamports(),
+    };
+    let measured_in = source_before.saturating_sub(source_after);
+    let measured_out = dest_after.saturating_sub(dest_before);
+    require!(measured_in > 0, FundError::InvalidAmount);
+    require!(measured_in <= in_amount, FundError::WithdrawalCapExceeded);
+    require!(measured_out >= out_min_amount, FundError::SlippageExceeded);
+    let others_after = fund_token_balances(ctx.remaining_accounts, &ctx.accounts.fund.key(), &declared)?;
+    require_no_losses(&others_before, &others_after)?;
+
+    // Record measured progress
+    let progress = &mut ctx.accounts.mint_progress;
+    progress.amount_liquidated = progress
+        .amount_liquidated
+        .checked_add(measured_in)
+        .ok_or(FundError::MathOverflow)?;
+    let ws = &mut ctx.accounts.withdrawal_state;
+    if progress.remaining() == 0 {
+        ws.positions_liquidated = ws.positions_liquidated.saturating_add(1);
+    }
+    ws.input_liquidated_sum = ws.input_liquidated_sum.saturating_add(measured_in);
+    ws.sol_accumulated = ws.sol_accumulated.saturating_add(measured_out);
+    // Stays Liquidating until the investor calls mark_withdrawal_ready
+    ws.transition_to(WithdrawalStatus::Liquidating)?;
 
-    // If any WSOL is accumulated externally and tracked, callers can separately bump ws.sol_accumulated via a dedicated instruction.
-    // For now we keep status as Initiated until the client marks ReadyToFinalize when done swapping.
+    emit!(WithdrawalSwapLeg {
+        fund: ctx.accounts.fund.key(),
+        investor: ws.investor,
+        input_mint: progress.mint,
+        amount_in: measured_in,
+        amount_out: measured_out,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
     Ok(())
 }
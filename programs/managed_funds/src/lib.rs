@@ -4,9 +4,14 @@ use anchor_lang::prelude::*;
 pub mod instructions;
 pub mod state;
 pub mod errors;
+pub mod events;
+pub mod mint_extensions;
 
 // Re-export context/account types so Anchor can find them at crate root
 pub use instructions::*;
+use state::{MintListMode, RedemptionMode, SwapAdapter};
+use spl_discriminator::SplDiscriminate;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
 
 declare_id!("DEFuNDoMVQ8TnYjDM95bJK55Myr5dmwor43xboG2XQYd");
 
@@ -27,6 +32,27 @@ macro_rules! log {
 pub mod managed_funds {
     use super::*;
 
+    /// One-time: create the global protocol config (program upgrade authority only)
+    pub fn initialize_protocol_config(
+        ctx: Context<InitializeProtocolConfig>,
+        treasury: Pubkey,
+        platform_fee_bps: u16,
+        treasury_performance_share_bps: u16,
+    ) -> Result<()> {
+        instructions::initialize_protocol_config(ctx, treasury, platform_fee_bps, treasury_performance_share_bps)
+    }
+
+    /// Update protocol admin, treasury and fee parameters (protocol admin only)
+    pub fn update_protocol_config(
+        ctx: Context<UpdateProtocolConfig>,
+        new_authority: Pubkey,
+        treasury: Pubkey,
+        platform_fee_bps: u16,
+        treasury_performance_share_bps: u16,
+    ) -> Result<()> {
+        instructions::update_protocol_config(ctx, new_authority, treasury, platform_fee_bps, treasury_performance_share_bps)
+    }
+
     /// Initialize a new fund vault
     pub fn initialize_fund(
         ctx: Context<InitializeFund>,
@@ -34,8 +60,10 @@ pub mod managed_funds {
         description: String,
         management_fee: u16, // basis points (100 = 1%)
         performance_fee: u16, // basis points
+        share_decimals: Option<u8>, // defaults to the base mint's decimals
+        share_metadata: Option<ShareMetadata>, // symbol and URI for Metaplex metadata on the shares mint
     ) -> Result<()> {
-        instructions::initialize_fund(ctx, name, description, management_fee, performance_fee)
+        instructions::initialize_fund(ctx, name, description, management_fee, performance_fee, share_decimals, share_metadata)
     }
 
     /// Deposit into a fund
@@ -43,21 +71,85 @@ pub mod managed_funds {
         instructions::deposit(ctx, amount)
     }
 
+    /// Deposit native SOL into a WSOL fund; lamports are wrapped into the vault on-chain
+    pub fn deposit_sol(ctx: Context<DepositSol>, amount: u64) -> Result<()> {
+        instructions::deposit_sol(ctx, amount)
+    }
+
     // Removed legacy withdraw, execute_trade, and update_fund instructions (unused in production)
 
     /// Initiate a withdrawal with position liquidation
-    pub fn initiate_withdrawal(
-        ctx: Context<InitiateWithdrawal>,
+    pub fn initiate_withdrawal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitiateWithdrawal<'info>>,
         shares_to_withdraw: u64,
     ) -> Result<()> {
         instructions::initiate_withdrawal(ctx, shares_to_withdraw)
     }
 
+    /// Investor marks liquidation done; the withdrawal becomes ReadyToFinalize
+    pub fn mark_withdrawal_ready(ctx: Context<MarkWithdrawalReady>) -> Result<()> {
+        instructions::mark_withdrawal_ready(ctx)
+    }
+
+    /// Investor cancels an unfinished withdrawal, closing its state and refunding rent
+    pub fn cancel_withdrawal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelWithdrawal<'info>>,
+    ) -> Result<()> {
+        instructions::cancel_withdrawal(ctx)
+    }
+
+    /// Expire a withdrawal older than the fund's timeout (permissionless)
+    pub fn expire_withdrawal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExpireWithdrawal<'info>>,
+    ) -> Result<()> {
+        instructions::expire_withdrawal(ctx)
+    }
+
+    /// Set the age after which unfinished withdrawals may be expired (manager only)
+    pub fn set_withdrawal_timeout(ctx: Context<SetWithdrawalTimeout>, timeout_secs: i64) -> Result<()> {
+        instructions::set_withdrawal_timeout(ctx, timeout_secs)
+    }
+
+    /// Switch between instant and epoch-batched redemptions and set the per-epoch gate (manager only)
+    pub fn set_redemption_mode(
+        ctx: Context<SetRedemptionMode>,
+        mode: RedemptionMode,
+        redemption_gate_bps: u16,
+        epoch_duration_secs: i64,
+    ) -> Result<()> {
+        instructions::set_redemption_mode(ctx, mode, redemption_gate_bps, epoch_duration_secs)
+    }
+
+    /// Settle the open redemption epoch at the current NAV and open the next one
+    pub fn settle_redemption_epoch(ctx: Context<SettleRedemptionEpoch>) -> Result<()> {
+        instructions::settle_redemption_epoch(ctx)
+    }
+
+    /// Claim base assets for a queued redemption after its epoch settles
+    pub fn claim_redemption(ctx: Context<ClaimRedemption>) -> Result<()> {
+        instructions::claim_redemption(ctx)
+    }
+
+    /// Redeem shares for the investor's pro-rata slice of every fund holding, without swapping (Instant redemption mode only)
+    pub fn redeem_in_kind<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RedeemInKind<'info>>,
+        shares: u64,
+    ) -> Result<()> {
+        instructions::redeem_in_kind(ctx, shares)
+    }
+
     /// Finalize withdrawal and distribute SOL
-    pub fn finalize_withdrawal(ctx: Context<FinalizeWithdrawal>) -> Result<()> {
+    pub fn finalize_withdrawal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeWithdrawal<'info>>,
+    ) -> Result<()> {
         instructions::finalize_withdrawal(ctx)
     }
 
+    /// Accrue the annual management fee and mint the owed shares to the manager
+    pub fn collect_management_fee(ctx: Context<CollectManagementFee>) -> Result<()> {
+        instructions::collect_management_fee(ctx)
+    }
+
     // Removed pay_rwa_investors (deferred for future implementation)
 
     /// Distribute SOL from vault to investors by share percentage, taking platform and performance fees.
@@ -81,19 +173,21 @@ pub mod managed_funds {
         instructions::initialize_vault(ctx)
     }
 
-    /// Forward Jupiter router instruction using vault PDA as program authority signer
+    /// Forward a registered swap program instruction using vault PDA as program authority signer.
+    /// Spends at most `in_amount` of the input mint and must yield at least `min_out` of the output mint.
     pub fn token_swap_vault<'info>(
         ctx: Context<'_, '_, 'info, 'info, TokenSwapVault<'info>>,
         data: Vec<u8>,
-        tmp: Vec<u8>,
+        in_amount: u64,
+        min_out: u64,
     ) -> Result<()> {
-        instructions::token_swap_vault(ctx, data, tmp)
+        instructions::token_swap_vault(ctx, data, in_amount, min_out)
     }
 
     // removed: liquidate_positions_batch (deleted)
     // removed: withdraw_swap_router (deleted)
 
-    /// Investor-only: forward a single Jupiter router swap for withdrawals.
+    /// Investor-only: forward a single registered swap program swap for withdrawals.
     pub fn withdraw_swap_instruction<'info>(
         ctx: Context<'_, '_, 'info, 'info, WithdrawSwapInstruction<'info>>,
         router_data: Vec<u8>,
@@ -144,13 +238,134 @@ pub mod managed_funds {
         instructions::pda_token_revoke(ctx)
     }
 
-    /// One-time: set the NAV attestor for this fund (manager only)
-    /// Investor-provided NAV attestation write (uses configured attestor key)
+    /// Set or rotate the NAV attestor for this fund (manager only)
+    pub fn set_nav_attestor(ctx: Context<SetNavAttestor>, attestor: Pubkey) -> Result<()> {
+        instructions::set_nav_attestor(ctx, attestor)
+    }
+
+    /// NAV attestation write (must be signed by the configured attestor key)
     pub fn nav_attest_write(ctx: Context<NavAttestWrite>, nav_value: u64, expires_at: i64) -> Result<()> {
         instructions::nav_attest_write(ctx, nav_value, expires_at)
     }
 
+    /// Register the fund's NAV attestors, agreement threshold and tolerance (manager only)
+    pub fn set_nav_quorum(
+        ctx: Context<SetNavQuorum>,
+        attestors: Vec<Pubkey>,
+        threshold: u8,
+        tolerance_bps: u16,
+    ) -> Result<()> {
+        instructions::set_nav_quorum(ctx, attestors, threshold, tolerance_bps)
+    }
+
+    /// NAV attestation write by one of the fund's quorum attestors
+    pub fn nav_quorum_attest(ctx: Context<NavQuorumAttest>, nav_value: u64, expires_at: i64) -> Result<()> {
+        instructions::nav_quorum_attest(ctx, nav_value, expires_at)
+    }
+
+    /// Publish a price for a mint on the protocol push feed (protocol authority only)
+    pub fn update_oracle_feed(ctx: Context<UpdateOracleFeed>, price: i64, conf: u64, expo: i32) -> Result<()> {
+        instructions::update_oracle_feed(ctx, price, conf, expo)
+    }
+
+    /// Switch the fund to oracle-computed NAV, or back to its attestor (manager only)
+    pub fn set_oracle_pricing(ctx: Context<SetOraclePricing>, enabled: bool) -> Result<()> {
+        instructions::set_oracle_pricing(ctx, enabled)
+    }
+
+    /// Recompute the fund NAV from its holdings and oracle prices
+    pub fn refresh_nav<'info>(ctx: Context<'_, '_, 'info, 'info, RefreshNav<'info>>) -> Result<()> {
+        instructions::refresh_nav(ctx)
+    }
+
+    /// Create the NAV history ring buffer for a fund created before it existed (manager only)
+    pub fn initialize_nav_history(ctx: Context<InitializeNavHistory>) -> Result<()> {
+        instructions::initialize_nav_history(ctx)
+    }
+
+    /// Grow a fund created under an older account layout to the current one (manager only)
+    pub fn migrate_fund(ctx: Context<MigrateFund>) -> Result<()> {
+        instructions::migrate_fund(ctx)
+    }
+
+    /// Grow an investor position created before the high-water mark to the current layout
+    pub fn migrate_position(ctx: Context<MigratePosition>) -> Result<()> {
+        instructions::migrate_position(ctx)
+    }
+
+    /// Grow a registered holding created before canonical holding accounts to the current layout
+    pub fn migrate_vault_position(ctx: Context<MigrateVaultPosition>) -> Result<()> {
+        instructions::migrate_vault_position(ctx)
+    }
+
+    /// Close a withdrawal (and its mint progress PDAs) opened under the layout without per-mint
+    /// allowances, refunding rent to the investor (investor only)
+    pub fn close_legacy_withdrawal<'info>(ctx: Context<'_, '_, 'info, 'info, CloseLegacyWithdrawal<'info>>) -> Result<()> {
+        instructions::close_legacy_withdrawal(ctx)
+    }
+
+    /// Grow a fund's NAV attestation PDA written before attestations recorded their attestor
+    pub fn migrate_nav_attestation(ctx: Context<MigrateNavAttestation>) -> Result<()> {
+        instructions::migrate_nav_attestation(ctx)
+    }
+
+    /// Set the maximum NAV move per window before deposits pause (manager only)
+    pub fn set_nav_circuit_breaker(
+        ctx: Context<SetNavCircuitBreaker>,
+        max_nav_change_bps: u16,
+        nav_window_secs: i64,
+    ) -> Result<()> {
+        instructions::set_nav_circuit_breaker(ctx, max_nav_change_bps, nav_window_secs)
+    }
+
+    /// Confirm a NAV after the circuit breaker tripped and resume deposits (manager or attestor)
+    pub fn confirm_nav(ctx: Context<ConfirmNav>, nav_value: u64) -> Result<()> {
+        instructions::confirm_nav(ctx, nav_value)
+    }
+
+    /// Create or update the fund's swap risk policy (manager only); loosening changes are timelocked
+    pub fn set_risk_policy(
+        ctx: Context<SetRiskPolicy>,
+        mint_list_mode: MintListMode,
+        mints: Vec<Pubkey>,
+        max_position_bps: u16,
+        max_trade_bps: u16,
+        max_trades_per_day: u16,
+    ) -> Result<()> {
+        instructions::set_risk_policy(ctx, mint_list_mode, mints, max_position_bps, max_trade_bps, max_trades_per_day)
+    }
+
+    /// Approve, update or disable a swap program usable by the fund swap instructions (protocol authority)
+    pub fn set_swap_program(ctx: Context<SetSwapProgram>, adapter: SwapAdapter, enabled: bool) -> Result<()> {
+        instructions::set_swap_program(ctx, adapter, enabled)
+    }
+
+    /// Open an empty investor position so a wallet can receive transferred shares (permissionless).
+    /// Required before any plain Token-2022 transfer of shares to a wallet that has no position;
+    /// transfer_shares opens it itself
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        instructions::open_position(ctx)
+    }
+
+    /// Token-2022 transfer hook of the shares mint: keeps investor positions in sync with share transfers.
+    /// Transfers to a wallet without an InvestorPosition fail; run open_position first or use transfer_shares
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn share_transfer_hook(ctx: Context<ShareTransferHook>, amount: u64) -> Result<()> {
+        instructions::share_transfer_hook(ctx, amount)
+    }
+
+    /// Transfer shares to any wallet, opening its shares account and position at the sender's expense
+    pub fn transfer_shares(ctx: Context<TransferShares>, amount: u64) -> Result<()> {
+        instructions::transfer_shares(ctx, amount)
+    }
+
+    /// Update the URI of the shares mint Metaplex metadata (manager only)
+    pub fn update_share_metadata_uri(ctx: Context<UpdateShareMetadataUri>, uri: String) -> Result<()> {
+        instructions::update_share_metadata_uri(ctx, uri)
+    }
+
     /// Close any number of zero-balance Fund-owned SPL token accounts and send lamports to the Fund WSOL ATA
+    /// (a canonical holding ATA must be followed by its VaultPosition PDA, which is closed with it)
     pub fn close_zero_token_accounts<'info>(ctx: Context<'_, '_, 'info, 'info, CloseZeroTokenAccounts<'info>>) -> Result<()> {
         instructions::close_zero_token_accounts(ctx)
     }
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+};
+use crate::errors::FundError;
+
+// Token-2022 mint extensions that break fund accounting:
+// - TransferFeeConfig: vaults receive less than the amount transferred
+// - TransferHook: transfers run third-party code and need extra accounts on every CPI
+// - NonTransferable: holdings can never be paid out or swapped
+// - PermanentDelegate: a third party can move tokens out of fund-owned accounts
+// - DefaultAccountState: new fund accounts may start frozen
+pub const UNSUPPORTED_MINT_EXTENSIONS: [ExtensionType; 5] = [
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::TransferHook,
+    ExtensionType::NonTransferable,
+    ExtensionType::PermanentDelegate,
+    ExtensionType::DefaultAccountState,
+];
+
+/// Reject mints the fund may not be denominated in or hold. Legacy SPL mints always pass.
+pub fn require_supported_mint(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    for extension in state.get_extension_types()? {
+        if UNSUPPORTED_MINT_EXTENSIONS.contains(&extension) {
+            msg!("unsupported mint extension {:?} on {}", extension, mint.key);
+            return err!(FundError::UnsupportedMintExtension);
+        }
+    }
+    Ok(())
+}
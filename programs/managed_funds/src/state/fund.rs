@@ -1,4 +1,9 @@
 use anchor_lang::prelude::*;
+use crate::errors::FundError;
+
+pub const SECONDS_PER_YEAR: i64 = 365 * 24 * 60 * 60;
+// Fixed-point scale for share prices (base units per share)
+pub const SHARE_PRICE_SCALE: u128 = 1_000_000_000;
 
 #[account]
 pub struct Fund {
@@ -17,6 +22,39 @@ pub struct Fund {
     pub bump: u8,                  // Fund PDA bump
     pub vault_bump: u8,            // Vault PDA bump
     pub shares_bump: u8,           // Shares mint PDA bump
+    pub pending_fee_shares: u64,   // Accrued management fee shares not yet minted to the manager
+    pub total_positions: u8,       // Number of registered VaultPosition accounts (non-base mints held)
+    pub withdrawal_timeout_secs: i64, // Age after which an unfinished withdrawal may be expired
+    pub redemption_mode: RedemptionMode, // Instant (per-investor liquidation) or Epoch (batched queue)
+    pub redemption_gate_bps: u16,  // Max share of NAV redeemable per epoch (10_000 = no gate)
+    pub epoch_duration_secs: i64,  // Minimum epoch length before anyone but the manager may settle it
+    pub current_epoch: u64,        // Index of the open RedemptionEpoch
+    pub nav_attestor: Pubkey,      // Key that must sign NAV attestations (default = none configured)
+    pub nav_source: NavSource,     // Where deposits and settlements take the NAV from
+    pub nav_updated_at: i64,       // Last oracle-computed NAV (refresh_nav)
+    pub max_nav_change_bps: u16,   // Max NAV move per window before deposits pause (0 = disabled)
+    pub nav_window_secs: i64,      // Length of the NAV change window
+    pub nav_window_start: i64,     // Start of the current window
+    pub nav_window_price: u64,     // Share price the current window's moves are measured against
+    pub deposits_paused: bool,     // Set by the circuit breaker until the manager or attestor confirms
+    pub trade_count: u64,          // Number of recorded manager swaps (next Trade sequence)
+    pub has_risk_policy: bool,     // Swaps must be checked against the fund's RiskPolicy
+    pub base_decimals: u8,         // Decimals of base_mint
+    pub share_decimals: u8,        // Decimals of shares_mint (defaults to base_decimals)
+    pub version: u8,               // Layout version; accounts below CURRENT_VERSION must run migrate_fund
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum NavSource {
+    Attestor, // Single configured attestor (optional per deposit)
+    Quorum,   // Median of the NavQuorum attestors
+    Oracle,   // Computed on-chain from holdings and price feeds by refresh_nav
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum RedemptionMode {
+    Instant,
+    Epoch,
 }
 
 impl Fund {
@@ -35,20 +73,171 @@ impl Fund {
         8 + // created_at
         1 + // bump
         1 + // vault_bump
-        1; // shares_bump
+        1 + // shares_bump
+        8 + // pending_fee_shares
+        1 + // total_positions
+        8 + // withdrawal_timeout_secs
+        1 + // redemption_mode
+        2 + // redemption_gate_bps
+        8 + // epoch_duration_secs
+        8 + // current_epoch
+        32 + // nav_attestor
+        1 + // nav_source
+        8 + // nav_updated_at
+        2 + // max_nav_change_bps
+        8 + // nav_window_secs
+        8 + // nav_window_start
+        8 + // nav_window_price
+        1 + // deposits_paused
+        8 + // trade_count
+        1 + // has_risk_policy
+        1 + // base_decimals
+        1 + // share_decimals
+        1; // version
+
+    // Version 0: any account created before the layout was versioned (fields after shares_bump
+    // may be missing). Version 1: the layout above.
+    pub const CURRENT_VERSION: u8 = 1;
+
+    pub const MAX_MANAGEMENT_FEE_BPS: u16 = 1000; // 10% per year
+    pub const MAX_PERFORMANCE_FEE_BPS: u16 = 5000; // 50% of gains
+    // Registry capacity. redeem_in_kind takes 4 accounts per position next to its 11 named accounts,
+    // the program and a compute budget instruction: 12 positions keep it within the 64-account lock limit.
+    pub const MAX_POSITIONS: u8 = 12;
+    pub const DEFAULT_WITHDRAWAL_TIMEOUT_SECS: i64 = 24 * 60 * 60;
+    pub const MIN_WITHDRAWAL_TIMEOUT_SECS: i64 = 60 * 60;
+    pub const MAX_ORACLE_NAV_AGE_SECS: i64 = 60;
+    pub const DEFAULT_MAX_NAV_CHANGE_BPS: u16 = 2500; // 25%
+    pub const DEFAULT_NAV_WINDOW_SECS: i64 = 60 * 60;
+    pub const MAX_BASE_DECIMALS: u8 = 18;
+    pub const MAX_SHARE_DECIMALS: u8 = 9;
 
     pub fn calculate_shares_to_mint(&self, deposit_amount: u64) -> u64 {
         // Safety: if there are no shares yet OR assets accounting is zero (e.g., after full payout),
         // treat as first deposit to avoid division by zero. This resets price anchor fairly.
         if self.total_shares == 0 || self.total_assets == 0 {
-            // First deposit or reset state: 1:1 ratio in base units
-            deposit_amount
+            // First deposit or reset state: one whole share per whole base token
+            (deposit_amount as u128 * self.share_unit() / self.base_unit()) as u64
         } else {
             // Calculate shares based on current share price
             (deposit_amount as u128 * self.total_shares as u128 / self.total_assets as u128) as u64
         }
     }
 
+    /// Accrue the management fee for the time elapsed since `last_fee_collection`.
+    /// The fee is taken by diluting existing holders: new shares worth `fee` at the current
+    /// NAV are added to `total_shares` and parked in `pending_fee_shares` until the manager collects them.
+    /// Returns the number of shares accrued by this call.
+    pub fn accrue_management_fee(&mut self, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(self.last_fee_collection);
+        if elapsed <= 0 {
+            return Ok(0);
+        }
+        if self.management_fee == 0 || self.total_shares == 0 || self.total_assets == 0 {
+            // Nothing to charge against; restart the accrual window
+            self.last_fee_collection = now;
+            return Ok(0);
+        }
+
+        let fee = (self.total_assets as u128)
+            .checked_mul(self.management_fee as u128)
+            .and_then(|v| v.checked_mul(elapsed as u128))
+            .ok_or(FundError::MathOverflow)?
+            / (10_000u128 * SECONDS_PER_YEAR as u128);
+        // Cap to keep the dilution formula well defined
+        let fee = fee.min(self.total_assets as u128 - 1);
+
+        // Shares s such that s / (total_shares + s) == fee / total_assets
+        let fee_shares = fee
+            .checked_mul(self.total_shares as u128)
+            .ok_or(FundError::MathOverflow)?
+            / (self.total_assets as u128 - fee);
+        if fee_shares == 0 {
+            // Too little time elapsed to be worth one share; keep accruing from the same start
+            return Ok(0);
+        }
+        let fee_shares = u64::try_from(fee_shares).map_err(|_| FundError::MathOverflow)?;
+
+        self.total_shares = self.total_shares.checked_add(fee_shares).ok_or(FundError::MathOverflow)?;
+        self.pending_fee_shares = self.pending_fee_shares.checked_add(fee_shares).ok_or(FundError::MathOverflow)?;
+        self.last_fee_collection = now;
+        Ok(fee_shares)
+    }
+
+    fn base_unit(&self) -> u128 {
+        10u128.pow(self.base_decimals as u32)
+    }
+
+    fn share_unit(&self) -> u128 {
+        10u128.pow(self.share_decimals as u32)
+    }
+
+    /// Current NAV per share (base units per share base unit) scaled by `SHARE_PRICE_SCALE`;
+    /// one whole base token per whole share when the fund is empty. Fails rather than saturate
+    /// when the price does not fit in a u64.
+    pub fn share_price(&self) -> Result<u64> {
+        let price = if self.total_shares == 0 || self.total_assets == 0 {
+            SHARE_PRICE_SCALE * self.base_unit() / self.share_unit()
+        } else {
+            self.total_assets as u128 * SHARE_PRICE_SCALE / self.total_shares as u128
+        };
+        u64::try_from(price).map_err(|_| error!(FundError::MathOverflow))
+    }
+
+    /// Share price a NAV of `nav` would give at the current share count.
+    pub fn price_at(&self, nav: u64) -> u64 {
+        if self.total_shares == 0 {
+            return 0;
+        }
+        (nav as u128 * SHARE_PRICE_SCALE / self.total_shares as u128).min(u64::MAX as u128) as u64
+    }
+
+    /// NAV circuit breaker: whether `new_nav` keeps the share price within `max_nav_change_bps` of
+    /// the price at the start of the current window. Measuring per share keeps deposits and
+    /// withdrawals, which move the NAV but not the price, from tripping it. A breach pauses
+    /// deposits; callers then drop the new value instead of failing so the pause persists.
+    pub fn check_nav_move(&mut self, new_nav: u64, now: i64) -> bool {
+        if now.saturating_sub(self.nav_window_start) >= self.nav_window_secs {
+            self.nav_window_start = now;
+            self.nav_window_price = self.price_at(self.total_assets);
+        }
+        let base = self.nav_window_price as u128;
+        if self.max_nav_change_bps == 0 || base == 0 || self.total_shares == 0 {
+            return true;
+        }
+        let within = (self.price_at(new_nav).abs_diff(self.nav_window_price) as u128) * 10_000
+            <= self.max_nav_change_bps as u128 * base;
+        if !within {
+            self.deposits_paused = true;
+        }
+        within
+    }
+
+    /// Apply a quorum NAV under the same guards as a single attestation: a value below the
+    /// recorded NAV or `vault_amount` is ignored, and a move beyond the circuit breaker pauses
+    /// deposits and keeps the recorded NAV. Returns whether the breaker accepted the value; callers
+    /// must not price anything on a breach but still succeed, so the pause persists.
+    pub fn apply_quorum_nav(&mut self, nav: u64, vault_amount: u64, now: i64) -> bool {
+        if !self.check_nav_move(nav, now) {
+            return false;
+        }
+        if nav >= self.total_assets.max(vault_amount) {
+            self.total_assets = nav;
+        }
+        true
+    }
+
+    /// Oracle-priced funds must have run refresh_nav recently (normally in the same transaction).
+    pub fn require_fresh_nav(&self, now: i64) -> Result<()> {
+        if self.nav_source == NavSource::Oracle {
+            require!(
+                now.saturating_sub(self.nav_updated_at) <= Self::MAX_ORACLE_NAV_AGE_SECS,
+                FundError::StaleNav
+            );
+        }
+        Ok(())
+    }
+
     pub fn calculate_withdrawal_amount(&self, shares_to_burn: u64) -> u64 {
         if self.total_shares == 0 {
             0
@@ -57,3 +246,215 @@ impl Fund {
         }
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// An empty fund with 9/9 decimals and no fees.
+    pub(crate) fn fund() -> Fund {
+        Fund {
+            manager: Pubkey::new_unique(),
+            name: "test".to_string(),
+            description: String::new(),
+            base_mint: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            shares_mint: Pubkey::new_unique(),
+            management_fee: 0,
+            performance_fee: 0,
+            total_shares: 0,
+            total_assets: 0,
+            last_fee_collection: 0,
+            created_at: 0,
+            bump: 255,
+            vault_bump: 255,
+            shares_bump: 255,
+            pending_fee_shares: 0,
+            total_positions: 0,
+            withdrawal_timeout_secs: Fund::DEFAULT_WITHDRAWAL_TIMEOUT_SECS,
+            redemption_mode: RedemptionMode::Instant,
+            redemption_gate_bps: 10_000,
+            epoch_duration_secs: 0,
+            current_epoch: 0,
+            nav_attestor: Pubkey::default(),
+            nav_source: NavSource::Attestor,
+            nav_updated_at: 0,
+            max_nav_change_bps: Fund::DEFAULT_MAX_NAV_CHANGE_BPS,
+            nav_window_secs: Fund::DEFAULT_NAV_WINDOW_SECS,
+            nav_window_start: 0,
+            nav_window_price: 0,
+            deposits_paused: false,
+            trade_count: 0,
+            has_risk_policy: false,
+            base_decimals: 9,
+            share_decimals: 9,
+            version: Fund::CURRENT_VERSION,
+        }
+    }
+
+    fn funded(total_assets: u64, total_shares: u64) -> Fund {
+        Fund { total_assets, total_shares, ..fund() }
+    }
+
+    #[test]
+    fn management_fee_dilutes_by_the_annual_rate() {
+        let mut f = Fund { management_fee: 200, ..funded(1_000_000, 1_000_000) };
+        let minted = f.accrue_management_fee(SECONDS_PER_YEAR).unwrap();
+        // 2% of 1_000_000 = 20_000 of value: s / (1_000_000 + s) == 20_000 / 1_000_000
+        assert_eq!(minted, 20_408);
+        assert_eq!(f.pending_fee_shares, 20_408);
+        assert_eq!(f.total_shares, 1_020_408);
+        assert_eq!(f.last_fee_collection, SECONDS_PER_YEAR);
+        let fee_value = f.calculate_withdrawal_amount(minted);
+        assert!((19_990..=20_000).contains(&fee_value));
+    }
+
+    #[test]
+    fn management_fee_ignores_non_positive_elapsed_time() {
+        let mut f = Fund { management_fee: 200, last_fee_collection: 100, ..funded(1_000, 1_000) };
+        assert_eq!(f.accrue_management_fee(100).unwrap(), 0);
+        assert_eq!(f.accrue_management_fee(50).unwrap(), 0);
+        assert_eq!(f.last_fee_collection, 100);
+        assert_eq!(f.total_shares, 1_000);
+    }
+
+    #[test]
+    fn management_fee_restarts_the_window_when_nothing_is_charged() {
+        let mut f = funded(1_000, 1_000);
+        assert_eq!(f.accrue_management_fee(500).unwrap(), 0);
+        assert_eq!(f.last_fee_collection, 500);
+
+        let mut empty = Fund { management_fee: 200, ..fund() };
+        assert_eq!(empty.accrue_management_fee(500).unwrap(), 0);
+        assert_eq!(empty.last_fee_collection, 500);
+    }
+
+    #[test]
+    fn management_fee_keeps_accruing_below_one_share() {
+        let mut f = Fund { management_fee: 100, ..funded(1_000, 1_000) };
+        assert_eq!(f.accrue_management_fee(60).unwrap(), 0);
+        assert_eq!(f.last_fee_collection, 0);
+        assert_eq!(f.total_shares, 1_000);
+    }
+
+    #[test]
+    fn management_fee_is_capped_below_the_whole_fund() {
+        // Twenty years at 10% would exceed the NAV; the fee stops one unit short of it
+        let mut f = Fund { management_fee: 1_000, ..funded(1_000, 1_000) };
+        assert_eq!(f.accrue_management_fee(20 * SECONDS_PER_YEAR).unwrap(), 999_000);
+    }
+
+    /// 1000 units over 1000 shares with a 25% breaker window opened at t=0.
+    fn breaker_fund() -> Fund {
+        let mut f = funded(1_000, 1_000);
+        f.nav_window_price = f.price_at(f.total_assets);
+        f
+    }
+
+    #[test]
+    fn share_price_of_an_empty_fund_is_one_token_per_share() {
+        assert_eq!(fund().share_price().unwrap(), 1_000_000_000);
+        assert_eq!(Fund { base_decimals: 6, share_decimals: 9, ..fund() }.share_price().unwrap(), 1_000_000);
+        assert_eq!(Fund { base_decimals: 18, share_decimals: 9, ..fund() }.share_price().unwrap(), 1_000_000_000_000_000_000);
+        // Shares left after a full payout price as a reset fund
+        assert_eq!(Fund { total_shares: 5, ..fund() }.share_price().unwrap(), 1_000_000_000);
+    }
+
+    #[test]
+    fn share_price_is_nav_per_share() {
+        assert_eq!(funded(3_000, 2_000).share_price().unwrap(), 1_500_000_000);
+        assert_eq!(funded(1, 3).share_price().unwrap(), 333_333_333);
+        assert_eq!(funded(u64::MAX, u64::MAX).share_price().unwrap(), 1_000_000_000);
+    }
+
+    #[test]
+    fn share_price_fails_instead_of_saturating() {
+        assert!(Fund { base_decimals: 18, share_decimals: 0, ..fund() }.share_price().is_err());
+        assert!(funded(u64::MAX, 1).share_price().is_err());
+        assert!(funded(u64::MAX / 1_000_000_000, 1).share_price().is_ok());
+    }
+
+    #[test]
+    fn price_at_scales_nav_per_share() {
+        let f = funded(1_000, 4_000);
+        assert_eq!(f.price_at(1_000), 250_000_000);
+        assert_eq!(f.price_at(0), 0);
+        assert_eq!(fund().price_at(1_000), 0);
+        assert_eq!(funded(1, 1).price_at(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn breaker_allows_moves_up_to_the_limit_either_way() {
+        let mut f = breaker_fund();
+        assert!(f.check_nav_move(1_250, 10));
+        assert!(f.check_nav_move(750, 10));
+        assert!(!f.deposits_paused);
+        assert!(!f.check_nav_move(1_251, 10));
+        assert!(f.deposits_paused);
+        assert!(!breaker_fund().check_nav_move(749, 10));
+    }
+
+    #[test]
+    fn breaker_measures_price_not_nav() {
+        // A deposit doubles NAV and shares; the price and the breaker are unaffected
+        let mut f = breaker_fund();
+        f.total_shares = 2_000;
+        assert!(f.check_nav_move(2_000, 10));
+        assert!(!f.check_nav_move(3_000, 10));
+    }
+
+    #[test]
+    fn breaker_rolls_the_window_from_the_recorded_nav() {
+        let mut f = breaker_fund();
+        f.total_assets = 1_200;
+        assert!(!f.check_nav_move(1_450, Fund::DEFAULT_NAV_WINDOW_SECS - 1));
+        let mut f = breaker_fund();
+        f.total_assets = 1_200;
+        assert!(f.check_nav_move(1_450, Fund::DEFAULT_NAV_WINDOW_SECS));
+        assert_eq!(f.nav_window_start, Fund::DEFAULT_NAV_WINDOW_SECS);
+        assert_eq!(f.nav_window_price, 1_200_000_000);
+    }
+
+    #[test]
+    fn breaker_is_off_when_disabled_or_empty() {
+        let mut f = Fund { max_nav_change_bps: 0, ..breaker_fund() };
+        assert!(f.check_nav_move(u64::MAX, 10));
+        let mut empty = fund();
+        assert!(empty.check_nav_move(u64::MAX, 10));
+        assert!(!empty.deposits_paused);
+    }
+
+    #[test]
+    fn quorum_nav_within_the_breaker_is_applied() {
+        let mut f = breaker_fund();
+        assert!(f.apply_quorum_nav(1_200, 0, 10));
+        assert_eq!(f.total_assets, 1_200);
+        assert!(!f.deposits_paused);
+    }
+
+    #[test]
+    fn quorum_nav_below_the_floor_is_ignored() {
+        let mut f = breaker_fund();
+        assert!(f.apply_quorum_nav(900, 0, 10));
+        assert_eq!(f.total_assets, 1_000);
+        assert!(f.apply_quorum_nav(1_050, 1_100, 10));
+        assert_eq!(f.total_assets, 1_000);
+    }
+
+    #[test]
+    fn quorum_nav_breach_keeps_the_old_nav_and_pauses() {
+        let mut f = breaker_fund();
+        assert!(!f.apply_quorum_nav(1_300, 0, 10));
+        assert_eq!(f.total_assets, 1_000);
+        assert!(f.deposits_paused);
+        assert!(!f.apply_quorum_nav(700, 0, 20));
+        assert_eq!(f.total_assets, 1_000);
+    }
+
+    #[test]
+    fn management_fee_reports_overflow() {
+        let mut f = Fund { management_fee: 1_000, ..funded(u64::MAX, u64::MAX) };
+        assert!(f.accrue_management_fee(i64::MAX).is_err());
+        assert_eq!(f.total_shares, u64::MAX);
+    }
+}
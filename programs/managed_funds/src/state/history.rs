@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use super::Fund;
+
+#[zero_copy]
+#[derive(Default)]
+pub struct NavSnapshot {
+    pub timestamp: i64,    // unix timestamp of the event
+    pub nav: u64,          // fund total_assets after the event (base units)
+    pub total_shares: u64, // fund total_shares after the event
+    pub share_price: u64,  // NAV per share (SHARE_PRICE_SCALE)
+}
+
+// Fixed-capacity ring of NAV snapshots, PDA of [b"nav_history", fund].
+// Appended on NAV refreshes, deposits, withdrawals and payouts; the oldest entry is overwritten.
+#[account(zero_copy)]
+pub struct NavHistory {
+    pub fund: Pubkey,
+    pub head: u32, // index the next snapshot is written to
+    pub len: u32,  // number of valid snapshots (<= CAPACITY)
+    pub entries: [NavSnapshot; 256],
+}
+
+impl NavHistory {
+    pub const CAPACITY: usize = 256;
+    pub const SPACE: usize = 8 + std::mem::size_of::<NavHistory>();
+
+    pub fn record(&mut self, fund: &Fund, now: i64) -> Result<()> {
+        self.entries[self.head as usize] = NavSnapshot {
+            timestamp: now,
+            nav: fund.total_assets,
+            total_shares: fund.total_shares,
+            share_price: fund.share_price()?,
+        };
+        self.head = ((self.head as usize + 1) % Self::CAPACITY) as u32;
+        if (self.len as usize) < Self::CAPACITY {
+            self.len += 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::fund::tests::fund;
+
+    fn history() -> Box<NavHistory> {
+        Box::new(NavHistory {
+            fund: Pubkey::new_unique(),
+            head: 0,
+            len: 0,
+            entries: [NavSnapshot::default(); NavHistory::CAPACITY],
+        })
+    }
+
+    #[test]
+    fn record_appends_a_snapshot_of_the_fund() {
+        let mut h = history();
+        let f = Fund { total_assets: 2_000, total_shares: 1_000, ..fund() };
+        h.record(&f, 42).unwrap();
+        assert_eq!((h.head, h.len), (1, 1));
+        let s = h.entries[0];
+        assert_eq!((s.timestamp, s.nav, s.total_shares, s.share_price), (42, 2_000, 1_000, 2_000_000_000));
+    }
+
+    #[test]
+    fn record_wraps_over_the_oldest_snapshot() {
+        let mut h = history();
+        let f = fund();
+        for t in 0..300 {
+            h.record(&f, t).unwrap();
+        }
+        assert_eq!(h.len as usize, NavHistory::CAPACITY);
+        assert_eq!(h.head, 300 - 256);
+        // head now points at the oldest surviving snapshot, just after the newest
+        assert_eq!(h.entries[h.head as usize].timestamp, 44);
+        assert_eq!(h.entries[h.head as usize - 1].timestamp, 299);
+        assert_eq!(h.entries[0].timestamp, 256);
+        assert_eq!(h.entries[NavHistory::CAPACITY - 1].timestamp, 255);
+    }
+
+    #[test]
+    fn record_wraps_exactly_at_capacity() {
+        let mut h = history();
+        let f = fund();
+        for t in 0..NavHistory::CAPACITY as i64 {
+            h.record(&f, t).unwrap();
+        }
+        assert_eq!((h.head, h.len as usize), (0, NavHistory::CAPACITY));
+        h.record(&f, 1_000).unwrap();
+        assert_eq!((h.head, h.len as usize), (1, NavHistory::CAPACITY));
+        assert_eq!(h.entries[0].timestamp, 1_000);
+    }
+
+    #[test]
+    fn failed_record_leaves_the_ring_untouched() {
+        let mut h = history();
+        let f = Fund { base_decimals: 18, share_decimals: 0, ..fund() };
+        assert!(h.record(&f, 1).is_err());
+        assert_eq!((h.head, h.len), (0, 0));
+        assert_eq!(h.entries[0].timestamp, 0);
+    }
+}
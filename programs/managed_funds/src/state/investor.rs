@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::errors::FundError;
+use crate::state::SHARE_PRICE_SCALE;
 
 #[account]
 pub struct InvestorPosition {
@@ -10,6 +12,7 @@ pub struct InvestorPosition {
     pub total_withdrawn: u64,     // Total amount withdrawn over time
     pub first_deposit_at: i64,    // Timestamp of first deposit
     pub last_activity_at: i64,    // Timestamp of last activity
+    pub high_water_mark: u64,     // Share price (SHARE_PRICE_SCALE) above which performance fee is charged
 }
 
 impl InvestorPosition {
@@ -21,7 +24,28 @@ impl InvestorPosition {
         8 + // total_deposited
         8 + // total_withdrawn
         8 + // first_deposit_at
-        8; // last_activity_at
+        8 + // last_activity_at
+        8; // high_water_mark
+
+    /// Blend the mark with newly acquired shares at `price`, weighting by share count.
+    pub fn record_entry(&mut self, new_shares: u64, price: u64) -> Result<()> {
+        let total = self.shares as u128 + new_shares as u128;
+        if total == 0 {
+            return Ok(());
+        }
+        let weighted = (self.shares as u128)
+            .checked_mul(self.high_water_mark as u128)
+            .and_then(|v| v.checked_add((new_shares as u128).checked_mul(price as u128)?))
+            .ok_or(FundError::MathOverflow)?;
+        self.high_water_mark = u64::try_from(weighted / total).map_err(|_| FundError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Portion of `value` (received for `shares`) that lies above the high-water mark.
+    pub fn gain_above_mark(&self, shares: u64, value: u64) -> u64 {
+        let basis = shares as u128 * self.high_water_mark as u128 / SHARE_PRICE_SCALE;
+        (value as u128).saturating_sub(basis) as u64
+    }
 
     pub fn current_value(&self, fund_total_assets: u64, fund_total_shares: u64) -> u64 {
         if fund_total_shares == 0 {
@@ -37,3 +61,59 @@ impl InvestorPosition {
         current_value as i64 - total_invested as i64
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn position(shares: u64, high_water_mark: u64) -> InvestorPosition {
+        InvestorPosition {
+            investor: Pubkey::new_unique(),
+            fund: Pubkey::new_unique(),
+            shares,
+            initial_investment: 0,
+            total_deposited: 0,
+            total_withdrawn: 0,
+            first_deposit_at: 0,
+            last_activity_at: 0,
+            high_water_mark,
+        }
+    }
+
+    #[test]
+    fn first_entry_sets_the_mark_to_the_entry_price() {
+        let mut p = position(0, 0);
+        p.record_entry(500, 1_250_000_000).unwrap();
+        assert_eq!(p.high_water_mark, 1_250_000_000);
+    }
+
+    #[test]
+    fn later_entries_are_weighted_by_share_count() {
+        let mut p = position(100, 1_000_000_000);
+        p.record_entry(300, 2_000_000_000).unwrap();
+        assert_eq!(p.high_water_mark, 1_750_000_000);
+    }
+
+    #[test]
+    fn empty_entry_leaves_the_mark_alone() {
+        let mut p = position(0, 7);
+        p.record_entry(0, 1_000_000_000).unwrap();
+        assert_eq!(p.high_water_mark, 7);
+    }
+
+    #[test]
+    fn entry_reports_overflow() {
+        let mut p = position(u64::MAX, u64::MAX);
+        assert!(p.record_entry(u64::MAX, u64::MAX).is_err());
+        assert_eq!(p.high_water_mark, u64::MAX);
+    }
+
+    #[test]
+    fn gain_is_measured_above_the_mark() {
+        let p = position(1_000, 1_500_000_000);
+        assert_eq!(p.gain_above_mark(1_000, 2_000), 500);
+        assert_eq!(p.gain_above_mark(500, 1_000), 250);
+        assert_eq!(p.gain_above_mark(1_000, 1_000), 0);
+        assert_eq!(position(u64::MAX, u64::MAX).gain_above_mark(u64::MAX, u64::MAX), 0);
+    }
+}
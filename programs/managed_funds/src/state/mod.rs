@@ -3,9 +3,21 @@ pub mod investor;
 pub mod trade;
 pub mod withdrawal;
 pub mod nav;
+pub mod protocol;
+pub mod redemption;
+pub mod oracle;
+pub mod history;
+pub mod risk;
+pub mod swap;
 
 pub use fund::*;
 pub use investor::*;
 pub use trade::*;
 pub use withdrawal::*;
 pub use nav::*;
+pub use protocol::*;
+pub use redemption::*;
+pub use oracle::*;
+pub use history::*;
+pub use risk::*;
+pub use swap::*;
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::errors::FundError;
 
 #[account]
 pub struct NavAttestation {
@@ -7,8 +8,215 @@ pub struct NavAttestation {
     pub expires_at: i64,     // unix timestamp when this attestation expires
     pub updated_at: i64,     // unix timestamp when written
     pub bump: u8,
+    pub attestor: Pubkey,    // attestor that signed the latest write
 }
 
 impl NavAttestation {
-    pub const SPACE: usize = 8 + 32 + 8 + 8 + 8 + 1;
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 8 + 1 + 32;
+    // Longest validity an attestation may claim
+    pub const MAX_TTL_SECS: i64 = 60 * 60;
+
+    /// Usable for `fund` at `now`: written by the fund's current attestor and not expired.
+    pub fn is_valid_for(&self, fund: &Pubkey, attestor: &Pubkey, now: i64) -> bool {
+        self.fund == *fund
+            && *attestor != Pubkey::default()
+            && self.attestor == *attestor
+            && self.expires_at >= now
+    }
+
+    /// Record a signed value, enforcing the TTL cap and strictly increasing write times so an
+    /// older attestation cannot be replayed over a newer one.
+    pub fn record(
+        &mut self,
+        fund: Pubkey,
+        attestor: Pubkey,
+        nav_value: u64,
+        expires_at: i64,
+        now: i64,
+        bump: u8,
+    ) -> Result<()> {
+        require!(
+            expires_at > now && expires_at - now <= Self::MAX_TTL_SECS,
+            FundError::StaleAttestation
+        );
+        require!(now > self.updated_at, FundError::StaleAttestation);
+
+        self.fund = fund;
+        self.nav_value = nav_value;
+        self.expires_at = expires_at;
+        self.updated_at = now;
+        self.bump = bump;
+        self.attestor = attestor;
+        Ok(())
+    }
+}
+
+// Multi-attestor configuration, PDA of [b"nav_quorum", fund].
+// Each attestor writes its own NavAttestation at [b"nav", fund, attestor].
+#[account]
+pub struct NavQuorum {
+    pub fund: Pubkey,
+    pub attestors: Vec<Pubkey>, // registered attestors (N)
+    pub threshold: u8,          // fresh attestations that must agree (M)
+    pub tolerance_bps: u16,     // max deviation from the median to count as agreeing
+    pub bump: u8,
+}
+
+impl NavQuorum {
+    pub const MAX_ATTESTORS: usize = 7;
+    pub const SPACE: usize = 8 + 32 + 4 + Self::MAX_ATTESTORS * 32 + 1 + 2 + 1;
+
+    /// Quorum NAV for `fund` from `[NavQuorum, NavAttestation...]` passed as remaining accounts.
+    pub fn nav_from_accounts(
+        fund: &Pubkey,
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+        now: i64,
+    ) -> Result<u64> {
+        let (quorum_ai, attestations) = accounts.split_first().ok_or(FundError::NavQuorumNotMet)?;
+        let (expected, _) = Pubkey::find_program_address(&[b"nav_quorum", fund.as_ref()], program_id);
+        require_keys_eq!(quorum_ai.key(), expected, FundError::NavQuorumNotMet);
+        require_keys_eq!(*quorum_ai.owner, *program_id, FundError::NavQuorumNotMet);
+        let mut data_slice: &[u8] = &quorum_ai.data.borrow();
+        let quorum = NavQuorum::try_deserialize(&mut data_slice)?;
+        quorum.aggregate(fund, attestations, program_id, now)
+    }
+
+    /// Median of the attestations of every registered attestor, provided at least `threshold` of
+    /// them lie within `tolerance_bps` of it. `attestation_accounts` must be the attestors' NAV
+    /// PDAs in registry order; a missing, foreign or expired attestation fails the quorum, so the
+    /// caller cannot pick which attestors are counted.
+    pub fn aggregate(
+        &self,
+        fund: &Pubkey,
+        attestation_accounts: &[AccountInfo],
+        program_id: &Pubkey,
+        now: i64,
+    ) -> Result<u64> {
+        require!(
+            !self.attestors.is_empty() && attestation_accounts.len() == self.attestors.len(),
+            FundError::NavQuorumNotMet
+        );
+        let mut values: Vec<u64> = Vec::with_capacity(self.attestors.len());
+        for (attestor, ai) in self.attestors.iter().zip(attestation_accounts) {
+            let (expected, _) = Pubkey::find_program_address(
+                &[b"nav", fund.as_ref(), attestor.as_ref()],
+                program_id,
+            );
+            require_keys_eq!(ai.key(), expected, FundError::NavQuorumNotMet);
+            require_keys_eq!(*ai.owner, *program_id, FundError::NavQuorumNotMet);
+            let mut data_slice: &[u8] = &ai.data.borrow();
+            let att = NavAttestation::try_deserialize(&mut data_slice)
+                .map_err(|_| error!(FundError::NavQuorumNotMet))?;
+            require!(att.is_valid_for(fund, attestor, now), FundError::NavQuorumNotMet);
+            values.push(att.nav_value);
+        }
+
+        values.sort_unstable();
+        let mid = values.len() / 2;
+        let median = if values.len() % 2 == 1 {
+            values[mid]
+        } else {
+            ((values[mid - 1] as u128 + values[mid] as u128) / 2) as u64
+        };
+        let agreeing = values
+            .iter()
+            .filter(|v| (v.abs_diff(median) as u128) * 10_000 <= self.tolerance_bps as u128 * median as u128)
+            .count();
+        require!(agreeing >= self.threshold as usize, FundError::NavQuorumNotMet);
+        Ok(median)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_000;
+
+    struct Attestation {
+        key: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    fn attestation(fund: &Pubkey, attestor: &Pubkey, nav_value: u64, expires_at: i64) -> Attestation {
+        let (key, bump) = Pubkey::find_program_address(&[b"nav", fund.as_ref(), attestor.as_ref()], &crate::ID);
+        let att = NavAttestation {
+            fund: *fund,
+            nav_value,
+            expires_at,
+            updated_at: NOW - 1,
+            bump,
+            attestor: *attestor,
+        };
+        let mut data = Vec::with_capacity(NavAttestation::SPACE);
+        att.try_serialize(&mut data).unwrap();
+        Attestation { key, lamports: 0, data }
+    }
+
+    fn quorum(fund: Pubkey, attestors: usize, threshold: u8, tolerance_bps: u16) -> NavQuorum {
+        NavQuorum {
+            fund,
+            attestors: (0..attestors).map(|_| Pubkey::new_unique()).collect(),
+            threshold,
+            tolerance_bps,
+            bump: 255,
+        }
+    }
+
+    fn aggregate(q: &NavQuorum, atts: &mut [Attestation]) -> Result<u64> {
+        let owner = crate::ID;
+        let infos: Vec<AccountInfo> = atts
+            .iter_mut()
+            .map(|a| AccountInfo::new(&a.key, false, false, &mut a.lamports, &mut a.data, &owner, false, 0))
+            .collect();
+        q.aggregate(&q.fund, &infos, &crate::ID, NOW)
+    }
+
+    fn attest(q: &NavQuorum, values: &[u64]) -> Vec<Attestation> {
+        q.attestors.iter().zip(values).map(|(a, v)| attestation(&q.fund, a, *v, NOW + 60)).collect()
+    }
+
+    #[test]
+    fn odd_quorum_takes_the_middle_value() {
+        let q = quorum(Pubkey::new_unique(), 3, 2, 100);
+        assert_eq!(aggregate(&q, &mut attest(&q, &[1_005, 1_000, 5_000])).unwrap(), 1_005);
+    }
+
+    #[test]
+    fn even_quorum_averages_the_middle_pair() {
+        let q = quorum(Pubkey::new_unique(), 4, 4, 100);
+        assert_eq!(aggregate(&q, &mut attest(&q, &[1_000, 1_004, 1_002, 998])).unwrap(), 1_001);
+        let big = quorum(Pubkey::new_unique(), 2, 2, 0);
+        assert_eq!(aggregate(&big, &mut attest(&big, &[u64::MAX, u64::MAX])).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn threshold_counts_values_within_tolerance() {
+        let q = quorum(Pubkey::new_unique(), 3, 3, 100);
+        assert!(aggregate(&q, &mut attest(&q, &[1_005, 1_000, 5_000])).is_err());
+        let q = quorum(Pubkey::new_unique(), 3, 3, 100);
+        assert_eq!(aggregate(&q, &mut attest(&q, &[1_010, 1_000, 990])).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn every_attestor_must_be_present_and_fresh() {
+        let q = quorum(Pubkey::new_unique(), 3, 1, 100);
+        let mut atts = attest(&q, &[1_000, 1_000, 1_000]);
+        assert!(aggregate(&q, &mut atts[..2]).is_err());
+
+        atts.swap(0, 1);
+        assert!(aggregate(&q, &mut atts).is_err());
+
+        let mut expired = attest(&q, &[1_000, 1_000]);
+        expired.push(attestation(&q.fund, &q.attestors[2], 1_000, NOW - 1));
+        assert!(aggregate(&q, &mut expired).is_err());
+    }
+
+    #[test]
+    fn empty_quorum_is_never_met() {
+        let q = quorum(Pubkey::new_unique(), 0, 0, 100);
+        assert!(aggregate(&q, &mut []).is_err());
+    }
 }
@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use crate::errors::FundError;
+
+/// Pyth-style price: `price * 10^expo` base-mint base units per base unit of the priced mint,
+/// with a `conf` interval in the same units.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+impl OraclePrice {
+    pub const MAX_STALENESS_SECS: i64 = 60;
+    pub const MAX_CONF_BPS: u64 = 200; // confidence interval must be within 2% of price
+    pub const MAX_ABS_EXPO: i32 = 18;
+
+    /// Value of `amount` at the lower edge of the confidence interval, in base-mint base units.
+    /// Rejects stale, non-positive or too-uncertain prices.
+    pub fn value_of(&self, amount: u64, now: i64) -> Result<u64> {
+        require!(
+            now.saturating_sub(self.publish_time) <= Self::MAX_STALENESS_SECS,
+            FundError::StaleOraclePrice
+        );
+        require!(self.price > 0 && self.expo.unsigned_abs() <= Self::MAX_ABS_EXPO as u32, FundError::InvalidOracle);
+        let price = self.price as u64;
+        require!(
+            (self.conf as u128) * 10_000 <= price as u128 * Self::MAX_CONF_BPS as u128,
+            FundError::OracleConfidenceTooWide
+        );
+
+        let scale = 10u128.pow(self.expo.unsigned_abs());
+        let gross = amount as u128 * (price - self.conf) as u128;
+        let value = if self.expo < 0 {
+            gross / scale
+        } else {
+            gross.checked_mul(scale).ok_or(FundError::MathOverflow)?
+        };
+        u64::try_from(value).map_err(|_| error!(FundError::MathOverflow))
+    }
+}
+
+/// Anything that can quote a mint against the fund's base asset. `OracleFeed` is the built-in
+/// push feed; adapters for external oracle accounts implement the same trait.
+pub trait PriceSource {
+    fn mint(&self) -> Pubkey;
+    fn latest_price(&self) -> OraclePrice;
+}
+
+// Push price feed maintained by the protocol authority, PDA of [b"oracle_feed", mint]
+#[account]
+pub struct OracleFeed {
+    pub mint: Pubkey,       // Priced mint
+    pub price: OraclePrice, // Latest published price
+    pub bump: u8,           // PDA bump
+}
+
+impl OracleFeed {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // mint
+        8 + 8 + 4 + 8 + // price (price, conf, expo, publish_time)
+        1; // bump
+}
+
+impl PriceSource for OracleFeed {
+    fn mint(&self) -> Pubkey {
+        self.mint
+    }
+
+    fn latest_price(&self) -> OraclePrice {
+        self.price
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_000;
+
+    fn price(price: i64, conf: u64, expo: i32) -> OraclePrice {
+        OraclePrice { price, conf, expo, publish_time: NOW }
+    }
+
+    #[test]
+    fn negative_exponent_scales_down_at_the_lower_confidence_edge() {
+        // 1.50 with a 0.01 interval: 1_000 units are worth 1.49 each
+        assert_eq!(price(150, 1, -2).value_of(1_000, NOW).unwrap(), 1_490);
+        // Values below one base unit round down
+        assert_eq!(price(150, 0, -2).value_of(1, NOW).unwrap(), 1);
+        assert_eq!(price(50, 0, -2).value_of(1, NOW).unwrap(), 0);
+    }
+
+    #[test]
+    fn zero_and_positive_exponents_scale_up() {
+        assert_eq!(price(3, 0, 0).value_of(7, NOW).unwrap(), 21);
+        assert_eq!(price(3, 0, 2).value_of(7, NOW).unwrap(), 2_100);
+    }
+
+    #[test]
+    fn exponent_is_bounded() {
+        assert!(price(1, 0, OraclePrice::MAX_ABS_EXPO).value_of(1, NOW).is_ok());
+        assert!(price(1, 0, -OraclePrice::MAX_ABS_EXPO).value_of(1, NOW).is_ok());
+        assert!(price(1, 0, OraclePrice::MAX_ABS_EXPO + 1).value_of(1, NOW).is_err());
+        assert!(price(1, 0, -OraclePrice::MAX_ABS_EXPO - 1).value_of(1, NOW).is_err());
+        assert!(price(1, 0, i32::MIN).value_of(1, NOW).is_err());
+    }
+
+    #[test]
+    fn rejects_unusable_prices() {
+        assert!(price(0, 0, 0).value_of(1, NOW).is_err());
+        assert!(price(-5, 0, 0).value_of(1, NOW).is_err());
+        // 3% interval against a 2% limit
+        assert!(price(100, 3, 0).value_of(1, NOW).is_err());
+        assert!(price(100, 2, 0).value_of(1, NOW).is_ok());
+    }
+
+    #[test]
+    fn rejects_stale_prices() {
+        let p = price(1, 0, 0);
+        assert!(p.value_of(1, NOW + OraclePrice::MAX_STALENESS_SECS).is_ok());
+        assert!(p.value_of(1, NOW + OraclePrice::MAX_STALENESS_SECS + 1).is_err());
+    }
+
+    #[test]
+    fn reports_overflow() {
+        assert!(price(i64::MAX, 0, 0).value_of(u64::MAX, NOW).is_err());
+        assert!(price(1, 0, 18).value_of(u64::MAX, NOW).is_err());
+        assert!(price(i64::MAX, 0, -18).value_of(u64::MAX, NOW).is_err());
+        assert_eq!(price(i64::MAX, 0, -18).value_of(1_000, NOW).unwrap(), 9_223);
+    }
+}
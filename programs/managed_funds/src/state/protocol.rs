@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct ProtocolConfig {
+    pub authority: Pubkey,                   // Admin allowed to update the config
+    pub treasury: Pubkey,                    // Wallet receiving platform fees
+    pub platform_fee_bps: u16,               // Platform fee on withdrawals and payouts
+    pub treasury_performance_share_bps: u16, // Share of performance fees routed to the treasury
+    pub bump: u8,                            // PDA bump
+}
+
+impl ProtocolConfig {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // authority
+        32 + // treasury
+        2 + // platform_fee_bps
+        2 + // treasury_performance_share_bps
+        1; // bump
+
+    pub const MAX_PLATFORM_FEE_BPS: u16 = 1000; // 10%
+
+    pub fn is_valid_fees(platform_fee_bps: u16, treasury_performance_share_bps: u16) -> bool {
+        platform_fee_bps <= Self::MAX_PLATFORM_FEE_BPS && treasury_performance_share_bps <= 10_000
+    }
+
+    pub fn platform_fee(&self, amount: u64) -> u64 {
+        (amount as u128 * self.platform_fee_bps as u128 / 10_000) as u64
+    }
+
+    pub fn treasury_performance_cut(&self, performance_fee: u64) -> u64 {
+        (performance_fee as u128 * self.treasury_performance_share_bps as u128 / 10_000) as u64
+    }
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum EpochStatus {
+    Open,
+    Settled,
+}
+
+// Redemption batch of an Epoch-mode fund, PDA of [b"epoch", fund, index (u64 LE)]
+#[account]
+pub struct RedemptionEpoch {
+    pub fund: Pubkey,         // Fund this epoch belongs to
+    pub index: u64,           // Sequential epoch number
+    pub opened_at: i64,       // Epoch open timestamp
+    pub status: EpochStatus,  // Open while accepting requests, Settled once priced
+    pub shares_queued: u64,   // Shares enqueued by investors (already burned from their wallets)
+    pub shares_redeemed: u64, // Shares redeemed at settlement (<= shares_queued after the gate)
+    pub payout_total: u64,    // Base assets set aside for redeemed shares (base units)
+    pub share_price: u64,     // NAV per share at settlement (SHARE_PRICE_SCALE)
+    pub settled_at: i64,      // Settlement timestamp
+    pub bump: u8,             // PDA bump
+}
+
+impl RedemptionEpoch {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // fund
+        8 + // index
+        8 + // opened_at
+        1 + // status
+        8 + // shares_queued
+        8 + // shares_redeemed
+        8 + // payout_total
+        8 + // share_price
+        8 + // settled_at
+        1; // bump
+
+    /// Shares of a request for `queued` shares that were redeemed after the gate.
+    pub fn redeemed_portion(&self, queued: u64) -> u64 {
+        if self.shares_queued == 0 {
+            0
+        } else {
+            (queued as u128 * self.shares_redeemed as u128 / self.shares_queued as u128) as u64
+        }
+    }
+
+    /// Base assets owed for `redeemed` shares of this epoch.
+    pub fn payout_for(&self, redeemed: u64) -> u64 {
+        if self.shares_redeemed == 0 {
+            0
+        } else {
+            (redeemed as u128 * self.payout_total as u128 / self.shares_redeemed as u128) as u64
+        }
+    }
+}
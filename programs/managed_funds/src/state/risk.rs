@@ -0,0 +1,220 @@
+use anchor_lang::prelude::*;
+use crate::errors::FundError;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum MintListMode {
+    Disabled, // any output mint
+    Allow,    // only listed output mints
+    Block,    // any output mint except the listed ones
+}
+
+// Optional manager swap limits, PDA of [b"risk_policy", fund]. Checked by token_swap_vault after the CPI.
+// Tightening applies at once; a change that allows anything the current policy forbids is staged in
+// `pending` and only takes effect after `LOOSEN_DELAY_SECS`, giving investors time to exit.
+#[account]
+pub struct RiskPolicy {
+    pub fund: Pubkey,
+    pub mint_list_mode: MintListMode,
+    pub mints: Vec<Pubkey>,       // allow or block list (the base mint is always allowed)
+    pub max_position_bps: u16,    // max share of NAV in any single non-base mint (0 = no limit)
+    pub max_trade_bps: u16,       // max trade input value as share of NAV (0 = no limit)
+    pub max_trades_per_day: u16,  // 0 = no limit
+    pub day: i64,                 // UTC day index of `trades_today`
+    pub trades_today: u16,
+    pub bump: u8,
+    pub pending: Option<RiskPolicyUpdate>, // staged loosening, applied once `effective_at` has passed
+}
+
+/// Policy parameters as submitted to set_risk_policy.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct RiskPolicyUpdate {
+    pub mint_list_mode: MintListMode,
+    pub mints: Vec<Pubkey>,
+    pub max_position_bps: u16,
+    pub max_trade_bps: u16,
+    pub max_trades_per_day: u16,
+    pub effective_at: i64,
+}
+
+impl RiskPolicyUpdate {
+    pub const SPACE: usize = 1 + // mint_list_mode
+        4 + RiskPolicy::MAX_MINTS * 32 + // mints
+        2 + // max_position_bps
+        2 + // max_trade_bps
+        2 + // max_trades_per_day
+        8; // effective_at
+}
+
+impl RiskPolicy {
+    pub const MAX_MINTS: usize = 16;
+    pub const LOOSEN_DELAY_SECS: i64 = 48 * 60 * 60;
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // fund
+        1 + // mint_list_mode
+        4 + Self::MAX_MINTS * 32 + // mints
+        2 + // max_position_bps
+        2 + // max_trade_bps
+        2 + // max_trades_per_day
+        8 + // day
+        2 + // trades_today
+        1 + // bump
+        1 + RiskPolicyUpdate::SPACE; // pending
+
+    pub fn allows_mint(&self, mint: &Pubkey) -> bool {
+        match self.mint_list_mode {
+            MintListMode::Disabled => true,
+            MintListMode::Allow => self.mints.contains(mint),
+            MintListMode::Block => !self.mints.contains(mint),
+        }
+    }
+
+    /// Whether `update` forbids at least everything the current policy forbids.
+    pub fn is_no_looser(&self, update: &RiskPolicyUpdate) -> bool {
+        // A zero limit means no limit
+        let limit_ok = |new: u16, old: u16| old == 0 || (new != 0 && new <= old);
+        let list_ok = match (&self.mint_list_mode, &update.mint_list_mode) {
+            (MintListMode::Disabled, _) => true,
+            (MintListMode::Allow, MintListMode::Allow) => update.mints.iter().all(|m| self.mints.contains(m)),
+            (MintListMode::Block, MintListMode::Allow) => !update.mints.iter().any(|m| self.mints.contains(m)),
+            (MintListMode::Block, MintListMode::Block) => self.mints.iter().all(|m| update.mints.contains(m)),
+            _ => false,
+        };
+        list_ok
+            && limit_ok(update.max_position_bps, self.max_position_bps)
+            && limit_ok(update.max_trade_bps, self.max_trade_bps)
+            && limit_ok(update.max_trades_per_day, self.max_trades_per_day)
+    }
+
+    /// Make `update` the active policy, dropping any staged change.
+    pub fn apply(&mut self, update: RiskPolicyUpdate) {
+        self.mint_list_mode = update.mint_list_mode;
+        self.mints = update.mints;
+        self.max_position_bps = update.max_position_bps;
+        self.max_trade_bps = update.max_trade_bps;
+        self.max_trades_per_day = update.max_trades_per_day;
+        self.pending = None;
+    }
+
+    /// Apply the staged change once its delay has passed.
+    pub fn apply_due(&mut self, now: i64) {
+        if self.pending.as_ref().is_some_and(|u| u.effective_at <= now) {
+            if let Some(update) = self.pending.take() {
+                self.apply(update);
+            }
+        }
+    }
+
+    /// Count one trade against today's limit.
+    pub fn record_trade(&mut self, now: i64) -> Result<()> {
+        let day = now.div_euclid(24 * 60 * 60);
+        if day != self.day {
+            self.day = day;
+            self.trades_today = 0;
+        }
+        self.trades_today = self.trades_today.saturating_add(1);
+        require!(
+            self.max_trades_per_day == 0 || self.trades_today <= self.max_trades_per_day,
+            FundError::RiskPolicyViolation
+        );
+        Ok(())
+    }
+
+    /// Whether `value` stays within `limit_bps` of `nav` (a zero limit or zero NAV disables the check).
+    pub fn within(value: u64, nav: u64, limit_bps: u16) -> bool {
+        limit_bps == 0 || nav == 0 || (value as u128) * 10_000 <= limit_bps as u128 * nav as u128
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(mode: MintListMode, mints: &[Pubkey], limits: (u16, u16, u16)) -> RiskPolicy {
+        RiskPolicy {
+            fund: Pubkey::new_unique(),
+            mint_list_mode: mode,
+            mints: mints.to_vec(),
+            max_position_bps: limits.0,
+            max_trade_bps: limits.1,
+            max_trades_per_day: limits.2,
+            day: 0,
+            trades_today: 0,
+            bump: 255,
+            pending: None,
+        }
+    }
+
+    fn update(mode: MintListMode, mints: &[Pubkey], limits: (u16, u16, u16)) -> RiskPolicyUpdate {
+        RiskPolicyUpdate {
+            mint_list_mode: mode,
+            mints: mints.to_vec(),
+            max_position_bps: limits.0,
+            max_trade_bps: limits.1,
+            max_trades_per_day: limits.2,
+            effective_at: 0,
+        }
+    }
+
+    const LIMITS: (u16, u16, u16) = (5_000, 1_000, 10);
+
+    #[test]
+    fn limits_may_only_tighten() {
+        let p = policy(MintListMode::Disabled, &[], LIMITS);
+        assert!(p.is_no_looser(&update(MintListMode::Disabled, &[], LIMITS)));
+        assert!(p.is_no_looser(&update(MintListMode::Disabled, &[], (4_000, 500, 1))));
+        assert!(!p.is_no_looser(&update(MintListMode::Disabled, &[], (5_001, 1_000, 10))));
+        assert!(!p.is_no_looser(&update(MintListMode::Disabled, &[], (5_000, 1_001, 10))));
+        assert!(!p.is_no_looser(&update(MintListMode::Disabled, &[], (5_000, 1_000, 11))));
+    }
+
+    #[test]
+    fn zero_means_no_limit() {
+        let p = policy(MintListMode::Disabled, &[], LIMITS);
+        assert!(!p.is_no_looser(&update(MintListMode::Disabled, &[], (0, 1_000, 10))));
+        assert!(!p.is_no_looser(&update(MintListMode::Disabled, &[], (5_000, 1_000, 0))));
+        let unlimited = policy(MintListMode::Disabled, &[], (0, 0, 0));
+        assert!(unlimited.is_no_looser(&update(MintListMode::Disabled, &[], (0, 0, 0))));
+        assert!(unlimited.is_no_looser(&update(MintListMode::Disabled, &[], (1, u16::MAX, 1))));
+    }
+
+    #[test]
+    fn allow_list_may_only_shrink() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let p = policy(MintListMode::Allow, &[a, b], LIMITS);
+        assert!(p.is_no_looser(&update(MintListMode::Allow, &[b], LIMITS)));
+        assert!(p.is_no_looser(&update(MintListMode::Allow, &[], LIMITS)));
+        assert!(!p.is_no_looser(&update(MintListMode::Allow, &[a, c], LIMITS)));
+        assert!(!p.is_no_looser(&update(MintListMode::Block, &[a, b], LIMITS)));
+        assert!(!p.is_no_looser(&update(MintListMode::Disabled, &[], LIMITS)));
+    }
+
+    #[test]
+    fn block_list_may_only_grow_or_become_a_disjoint_allow_list() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let p = policy(MintListMode::Block, &[a], LIMITS);
+        assert!(p.is_no_looser(&update(MintListMode::Block, &[a, b], LIMITS)));
+        assert!(!p.is_no_looser(&update(MintListMode::Block, &[b], LIMITS)));
+        assert!(p.is_no_looser(&update(MintListMode::Allow, &[b, c], LIMITS)));
+        assert!(!p.is_no_looser(&update(MintListMode::Allow, &[a], LIMITS)));
+        assert!(!p.is_no_looser(&update(MintListMode::Disabled, &[], LIMITS)));
+    }
+
+    #[test]
+    fn any_list_is_no_looser_than_none() {
+        let a = Pubkey::new_unique();
+        let p = policy(MintListMode::Disabled, &[], LIMITS);
+        assert!(p.is_no_looser(&update(MintListMode::Allow, &[a], LIMITS)));
+        assert!(p.is_no_looser(&update(MintListMode::Block, &[a], LIMITS)));
+    }
+
+    #[test]
+    fn staged_update_applies_once_due() {
+        let mut p = policy(MintListMode::Disabled, &[], LIMITS);
+        p.pending = Some(RiskPolicyUpdate { effective_at: 100, ..update(MintListMode::Disabled, &[], (0, 0, 0)) });
+        p.apply_due(99);
+        assert_eq!(p.max_position_bps, 5_000);
+        p.apply_due(100);
+        assert_eq!((p.max_position_bps, p.max_trade_bps, p.max_trades_per_day), (0, 0, 0));
+        assert!(p.pending.is_none());
+    }
+}
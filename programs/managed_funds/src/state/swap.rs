@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::errors::FundError;
+
+/// Protocol-approved swap program the fund PDA may sign for; managed by the protocol authority.
+#[account]
+pub struct SwapProgram {
+    pub program_id: Pubkey,   // Router or DEX program invoked by the swap instructions
+    pub adapter: SwapAdapter, // Account layout and balance measurement rules for that program
+    pub enabled: bool,        // Disabled entries are rejected without closing the PDA
+    pub bump: u8,             // PDA bump
+}
+
+impl SwapProgram {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // program_id
+        1 + // adapter
+        1 + // enabled
+        1; // bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum SwapAdapter {
+    Jupiter,    // Aggregator route / shared_accounts_route
+    PumpFun,    // Bonding curve sell only (see `supports_input`)
+    MeteoraDbc, // Dynamic bonding curve swap
+    Raydium,    // AMM v4 swap_base_in / swap_base_out
+}
+
+impl SwapAdapter {
+    /// Fewest accounts the program's swap instruction takes.
+    pub fn min_accounts(&self) -> usize {
+        match self {
+            SwapAdapter::Jupiter => 9,
+            SwapAdapter::PumpFun => 12,
+            SwapAdapter::MeteoraDbc => 15,
+            SwapAdapter::Raydium => 17,
+        }
+    }
+
+    /// Whether the swap authority (the fund PDA) may sit at `index` of an instruction with `len` accounts.
+    fn is_authority_slot(&self, index: usize, len: usize) -> bool {
+        match self {
+            // user_transfer_authority: 1 in route, 2 in shared_accounts_route
+            SwapAdapter::Jupiter => index == 1 || index == 2,
+            SwapAdapter::PumpFun => index == 6,
+            SwapAdapter::MeteoraDbc => index == 9,
+            // user_source_owner closes the list
+            SwapAdapter::Raydium => index + 1 == len,
+        }
+    }
+
+    /// Pump.fun sells pay SOL straight to the user as lamports rather than into a WSOL account,
+    /// so such output is measured on the fund PDA lamports.
+    pub fn pays_native_lamports(&self, output_mint: &Pubkey) -> bool {
+        *self == SwapAdapter::PumpFun && *output_mint == anchor_spl::token::spl_token::native_mint::id()
+    }
+
+    /// Pump.fun buys pay SOL out of the user's system account, which the data-carrying fund PDA
+    /// cannot do, so that adapter only sells into SOL and never spends the native mint.
+    pub fn supports_input(&self, input_mint: &Pubkey) -> bool {
+        !(*self == SwapAdapter::PumpFun && *input_mint == anchor_spl::token::spl_token::native_mint::id())
+    }
+
+    /// Check the forwarded account list: long enough for the program, the fund PDA in the
+    /// authority slot and every fund account the caller measures passed as writable.
+    pub fn validate_accounts(
+        &self,
+        accounts: &[AccountInfo],
+        authority: &Pubkey,
+        measured: &[Pubkey],
+    ) -> Result<()> {
+        require!(accounts.len() >= self.min_accounts(), FundError::InvalidSwapAccounts);
+        let authority_ok = accounts
+            .iter()
+            .enumerate()
+            .any(|(i, acc)| acc.key == authority && self.is_authority_slot(i, accounts.len()));
+        require!(authority_ok, FundError::InvalidSwapAccounts);
+        for key in measured {
+            require!(
+                accounts.iter().any(|acc| acc.key == key && acc.is_writable),
+                FundError::InvalidSwapAccounts
+            );
+        }
+        Ok(())
+    }
+}
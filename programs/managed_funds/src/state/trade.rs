@@ -2,8 +2,9 @@ use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub enum TradeType {
-    Buy,
-    Sell,
+    Buy,  // base mint in
+    Sell, // base mint out
+    Swap, // neither side is the base mint
 }
 
 #[account]
@@ -16,7 +17,8 @@ pub struct Trade {
     pub amount_in: u64,           // Amount of input tokens
     pub amount_out: u64,          // Amount of output tokens received
     pub timestamp: i64,           // Trade execution timestamp
-    pub signature: String,        // Transaction signature for verification
+    pub sequence: u64,            // Fund-level trade number, PDA of [b"trade", fund, sequence (u64 LE)]
+    pub bump: u8,                 // PDA bump
 }
 
 impl Trade {
@@ -29,5 +31,16 @@ impl Trade {
         8 + // amount_in
         8 + // amount_out
         8 + // timestamp
-        4 + 88; // signature (String with length prefix, max 88 chars for tx sig)
+        8 + // sequence
+        1; // bump
+
+    pub fn classify(base_mint: &Pubkey, input_mint: &Pubkey, output_mint: &Pubkey) -> TradeType {
+        if input_mint == base_mint {
+            TradeType::Buy
+        } else if output_mint == base_mint {
+            TradeType::Sell
+        } else {
+            TradeType::Swap
+        }
+    }
 }
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::state::Fund;
+use crate::errors::FundError;
 
 #[account]
 pub struct WithdrawalState {
@@ -16,6 +18,15 @@ pub struct WithdrawalState {
     pub status: WithdrawalStatus,  // Current status
     pub created_at: i64,           // Withdrawal request timestamp
     pub bump: u8,                  // PDA bump
+    pub allowances: Vec<MintAllowance>, // Per-mint liquidation allowance snapshotted at initiation
+    pub epoch: u64,                // Redemption epoch of a queued request (Epoch-mode funds)
+    pub high_water_mark: u64,      // Investor mark snapshotted when the request was queued
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct MintAllowance {
+    pub mint: Pubkey,        // Registered position mint
+    pub amount_allowed: u64, // fraction_bps of the fund's holding at initiation (mint base units)
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
@@ -25,6 +36,35 @@ pub enum WithdrawalStatus {
     ReadyToFinalize,
     Completed,
     Failed,
+    Queued,
+}
+
+impl WithdrawalStatus {
+    /// Allowed transitions:
+    /// Initiated -> Liquidating | ReadyToFinalize | Failed
+    /// Liquidating -> Liquidating | ReadyToFinalize | Failed
+    /// ReadyToFinalize -> Completed | Failed
+    /// Queued -> Completed (claimed after its epoch settles)
+    /// Completed and Failed are terminal.
+    pub fn can_transition_to(&self, next: &WithdrawalStatus) -> bool {
+        use WithdrawalStatus::*;
+        matches!(
+            (self, next),
+            (Initiated, Liquidating)
+                | (Initiated, ReadyToFinalize)
+                | (Initiated, Failed)
+                | (Liquidating, Liquidating)
+                | (Liquidating, ReadyToFinalize)
+                | (Liquidating, Failed)
+                | (ReadyToFinalize, Completed)
+                | (ReadyToFinalize, Failed)
+                | (Queued, Completed)
+        )
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, WithdrawalStatus::Completed | WithdrawalStatus::Failed)
+    }
 }
 
 impl WithdrawalState {
@@ -42,17 +82,47 @@ impl WithdrawalState {
         8 + // input_liquidated_sum
         1 + // status
         8 + // created_at
-        1; // bump
+        1 + // bump
+        4 + Fund::MAX_POSITIONS as usize * (32 + 8) + // allowances
+        8 + // epoch
+        8; // high_water_mark
+
+    pub fn allowance_for(&self, mint: &Pubkey) -> Option<u64> {
+        self.allowances.iter().find(|a| &a.mint == mint).map(|a| a.amount_allowed)
+    }
+
+    pub fn transition_to(&mut self, next: WithdrawalStatus) -> Result<()> {
+        require!(self.status.can_transition_to(&next), FundError::InvalidWithdrawalStatus);
+        self.status = next;
+        Ok(())
+    }
+
+    /// Close the given WithdrawalMintProgress PDAs of the withdrawal at `withdrawal_key`,
+    /// refunding their rent to `destination`.
+    pub fn close_progress_accounts<'info>(
+        withdrawal_key: &Pubkey,
+        progress_accounts: &'info [AccountInfo<'info>],
+        destination: &AccountInfo<'info>,
+    ) -> Result<()> {
+        for progress_ai in progress_accounts.iter() {
+            let progress: Account<WithdrawalMintProgress> = Account::try_from(progress_ai)?;
+            require_keys_eq!(progress.withdrawal, *withdrawal_key, FundError::InvalidInput);
+            progress.close(destination.clone())?;
+        }
+        Ok(())
+    }
 }
 
+// Registry entry for a non-base mint held by the fund, PDA of [b"vault_position", fund, mint]
 #[account]
 pub struct VaultPosition {
-    pub vault: Pubkey,        // Fund vault
+    pub vault: Pubkey,        // Fund (PDA) holding the position
     pub mint: Pubkey,         // Token mint
     pub amount: u64,          // Amount held
     pub created_at: i64,      // Position creation timestamp
     pub last_updated: i64,    // Last update timestamp
     pub bump: u8,             // PDA bump
+    pub token_account: Pubkey, // Canonical holding account: the fund's ATA for `mint`
 }
 
 impl VaultPosition {
@@ -62,15 +132,30 @@ impl VaultPosition {
         8 + // amount
         8 + // created_at
         8 + // last_updated
-        1; // bump
+        1 + // bump
+        32; // token_account
+
+    /// Only the canonical holding account counts as the fund's balance of the mint; other
+    /// fund-owned accounts of the same mint (anyone can create one) are ignored by pricing and exits.
+    pub fn require_holding(&self, token_account: &Pubkey) -> Result<()> {
+        require_keys_eq!(*token_account, self.token_account, FundError::InvalidHoldingAccount);
+        Ok(())
+    }
+
+    pub fn refresh(&mut self, amount: u64, now: i64) {
+        self.amount = amount;
+        self.last_updated = now;
+    }
 }
 
+// Per-leg progress, PDA of [b"withdrawal_mint", withdrawal_state, mint]
 #[account]
 pub struct WithdrawalMintProgress {
     pub withdrawal: Pubkey,     // WithdrawalState key
     pub mint: Pubkey,           // Token mint being liquidated
     pub amount_liquidated: u64, // Total input amount liquidated so far (in mint base units)
     pub bump: u8,               // PDA bump
+    pub amount_allowed: u64,    // Cap copied from the WithdrawalState allowance for this mint
 }
 
 impl WithdrawalMintProgress {
@@ -78,5 +163,10 @@ impl WithdrawalMintProgress {
         32 + // withdrawal
         32 + // mint
         8 +  // amount_liquidated
-        1;   // bump
+        1 +  // bump
+        8;   // amount_allowed
+
+    pub fn remaining(&self) -> u64 {
+        self.amount_allowed.saturating_sub(self.amount_liquidated)
+    }
 }
@@ -0,0 +1,20 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Dump the on-chain programs the local validator loads through [[test.genesis]] in Anchor.toml.
+# Required before the first `anchor test`: the binaries are not committed (tests/fixtures/*.so is
+# ignored) and the validator does not start without them. Needs the solana CLI and mainnet access;
+# programs already present are kept. `npm run anchor:test` runs this first.
+FIXTURES="$(cd "$(dirname "$0")/.." && pwd)/tests/fixtures"
+mkdir -p "$FIXTURES"
+
+dump() {
+  if [ -s "$FIXTURES/$2" ]; then
+    echo "tests/fixtures/$2 present, skipping"
+  else
+    solana program dump -u m "$1" "$FIXTURES/$2"
+  fi
+}
+
+# Metaplex Token Metadata (share metadata tests)
+dump metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s mpl_token_metadata.so
@@ -0,0 +1,96 @@
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  FundAccounts,
+  connection,
+  createWsolFund,
+  depositSol,
+  ensureProtocolConfig,
+  expectFundError,
+  fundedKeypair,
+  positionAddress,
+  program,
+  sendLamports,
+  sharesAta,
+  withdrawalAddress,
+} from "./helpers";
+
+describe("finalize_withdrawal fee recipient", () => {
+  const PERFORMANCE_FEE_BPS = 2000;
+  let f: FundAccounts;
+  let investor: Keypair;
+  let protocolConfig: Awaited<ReturnType<typeof ensureProtocolConfig>>;
+
+  before(async () => {
+    protocolConfig = await ensureProtocolConfig();
+    f = await createWsolFund(await fundedKeypair(), PERFORMANCE_FEE_BPS);
+    investor = await fundedKeypair();
+
+    // 1 SOL in, then 1.5 SOL lands on the fund PDA outside any liquidation leg
+    await depositSol(f, investor, LAMPORTS_PER_SOL);
+    await sendLamports(f.fund, 1.5 * LAMPORTS_PER_SOL);
+
+    const position = await program.account.investorPosition.fetch(positionAddress(f.fund, investor.publicKey));
+    await program.methods
+      .initiateWithdrawal(position.shares.divn(2))
+      .accountsPartial({
+        fund: f.fund,
+        investorPosition: positionAddress(f.fund, investor.publicKey),
+        withdrawalState: withdrawalAddress(f.fund, investor.publicKey),
+        investor: investor.publicKey,
+        redemptionEpoch: null,
+        sharesMint: null,
+        investorSharesAccount: null,
+        tokenProgram: null,
+      })
+      .signers([investor])
+      .rpc();
+    await program.methods
+      .markWithdrawalReady()
+      .accountsPartial({
+        fund: f.fund,
+        withdrawalState: withdrawalAddress(f.fund, investor.publicKey),
+        investor: investor.publicKey,
+      })
+      .signers([investor])
+      .rpc();
+  });
+
+  const finalize = (trader: Keypair["publicKey"]) =>
+    program.methods
+      .finalizeWithdrawal()
+      .accountsPartial({
+        fund: f.fund,
+        investorPosition: positionAddress(f.fund, investor.publicKey),
+        sharesMint: f.sharesMint,
+        investorSharesAccount: sharesAta(f, investor.publicKey),
+        withdrawalState: withdrawalAddress(f.fund, investor.publicKey),
+        investor: investor.publicKey,
+        trader,
+        protocolConfig: protocolConfig.protocolConfig,
+        treasury: protocolConfig.config.treasury,
+        navHistory: f.navHistory,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([investor])
+      .rpc();
+
+  it("rejects a performance fee recipient other than the manager", async () => {
+    await expectFundError(finalize(Keypair.generate().publicKey), "InvalidFeeRecipient");
+  });
+
+  it("pays nothing from lamports no liquidation leg measured", async () => {
+    const ws = await program.account.withdrawalState.fetch(withdrawalAddress(f.fund, investor.publicKey));
+    expect(ws.solAccumulated.toNumber()).to.equal(0);
+    const fundBefore = await connection.getBalance(f.fund);
+    const managerBefore = await connection.getBalance(f.manager.publicKey);
+
+    await finalize(f.manager.publicKey);
+
+    expect(await connection.getBalance(f.fund)).to.equal(fundBefore);
+    expect(await connection.getBalance(f.manager.publicKey)).to.equal(managerBefore);
+    const position = await program.account.investorPosition.fetch(positionAddress(f.fund, investor.publicKey));
+    expect(position.shares.toNumber()).to.be.greaterThan(0);
+  });
+});
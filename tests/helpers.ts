@@ -0,0 +1,251 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import {
+  AccountMeta,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  NATIVE_MINT,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  createTransferInstruction,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { ManagedFunds } from "../target/types/managed_funds";
+
+// Shared setup for the program tests. Everything runs against the local validator started by
+// `anchor test`; the provider wallet is the program's upgrade authority there.
+
+export const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+export const program = anchor.workspace.ManagedFunds as Program<ManagedFunds>;
+export const connection = provider.connection;
+
+const BPF_LOADER_UPGRADEABLE = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
+export const pda = (seeds: (Buffer | Uint8Array)[], programId = program.programId): PublicKey =>
+  PublicKey.findProgramAddressSync(seeds, programId)[0];
+
+export async function fundedKeypair(sol = 10): Promise<Keypair> {
+  const kp = Keypair.generate();
+  const sig = await connection.requestAirdrop(kp.publicKey, sol * LAMPORTS_PER_SOL);
+  await connection.confirmTransaction(sig, "confirmed");
+  return kp;
+}
+
+export async function sendLamports(to: PublicKey, lamports: number): Promise<void> {
+  const tx = new Transaction().add(
+    SystemProgram.transfer({ fromPubkey: provider.wallet.publicKey, toPubkey: to, lamports })
+  );
+  await provider.sendAndConfirm(tx);
+}
+
+/** Create the singleton ProtocolConfig on first use; later callers get the existing one. */
+export async function ensureProtocolConfig() {
+  const protocolConfig = pda([Buffer.from("protocol_config")]);
+  const existing = await program.account.protocolConfig.fetchNullable(protocolConfig);
+  if (existing) {
+    return { protocolConfig, config: existing };
+  }
+  const treasury = Keypair.generate().publicKey;
+  await program.methods
+    .initializeProtocolConfig(treasury, 100, 2000)
+    .accountsPartial({
+      protocolConfig,
+      program: program.programId,
+      programData: pda([program.programId.toBuffer()], BPF_LOADER_UPGRADEABLE),
+      authority: provider.wallet.publicKey,
+    })
+    .rpc();
+  return { protocolConfig, config: await program.account.protocolConfig.fetch(protocolConfig) };
+}
+
+export interface FundAccounts {
+  manager: Keypair;
+  name: string;
+  fund: PublicKey;
+  vault: PublicKey;
+  sharesMint: PublicKey;
+  navHistory: PublicKey;
+  baseMint: PublicKey;
+}
+
+export function fundAddresses(manager: PublicKey, name: string) {
+  const fund = pda([Buffer.from("fund"), manager.toBuffer(), Buffer.from(name)]);
+  const sharesMint = pda([Buffer.from("shares"), fund.toBuffer()]);
+  return {
+    fund,
+    vault: pda([Buffer.from("vault"), fund.toBuffer()]),
+    sharesMint,
+    extraAccountMetaList: pda([Buffer.from("extra-account-metas"), sharesMint.toBuffer()]),
+    navHistory: pda([Buffer.from("nav_history"), fund.toBuffer()]),
+  };
+}
+
+export const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+export const shareMetadataAddress = (sharesMint: PublicKey) =>
+  pda([Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), sharesMint.toBuffer()], TOKEN_METADATA_PROGRAM_ID);
+
+/** Initialize a WSOL-denominated fund with the given performance fee and optional share metadata. */
+export async function createWsolFund(
+  manager: Keypair,
+  performanceFeeBps = 2000,
+  shareMetadata: { symbol: string; uri: string } | null = null
+): Promise<FundAccounts> {
+  const name = `fund-${Math.random().toString(36).slice(2, 10)}`;
+  const addrs = fundAddresses(manager.publicKey, name);
+  await program.methods
+    .initializeFund(name, "test fund", 0, performanceFeeBps, null, shareMetadata)
+    .accountsPartial({
+      ...addrs,
+      baseMint: NATIVE_MINT,
+      manager: manager.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      sharesTokenProgram: TOKEN_2022_PROGRAM_ID,
+      shareMetadata: shareMetadata ? shareMetadataAddress(addrs.sharesMint) : null,
+      tokenMetadataProgram: shareMetadata ? TOKEN_METADATA_PROGRAM_ID : null,
+    })
+    .signers([manager])
+    .rpc();
+  return { manager, name, baseMint: NATIVE_MINT, ...addrs };
+}
+
+export const positionAddress = (fund: PublicKey, investor: PublicKey) =>
+  pda([Buffer.from("position"), investor.toBuffer(), fund.toBuffer()]);
+
+export const withdrawalAddress = (fund: PublicKey, investor: PublicKey) =>
+  pda([Buffer.from("withdrawal"), fund.toBuffer(), investor.toBuffer()]);
+
+export const sharesAta = (f: FundAccounts, owner: PublicKey) =>
+  getAssociatedTokenAddressSync(f.sharesMint, owner, false, TOKEN_2022_PROGRAM_ID);
+
+/** Deposit native SOL into a WSOL fund through deposit_sol. */
+export async function depositSol(f: FundAccounts, investor: Keypair, lamports: number): Promise<void> {
+  await program.methods
+    .depositSol(new BN(lamports))
+    .accountsPartial({
+      fund: f.fund,
+      vault: f.vault,
+      sharesMint: f.sharesMint,
+      investorPosition: positionAddress(f.fund, investor.publicKey),
+      investorSharesAccount: sharesAta(f, investor.publicKey),
+      investor: investor.publicKey,
+      navHistory: f.navHistory,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      sharesTokenProgram: TOKEN_2022_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .signers([investor])
+    .rpc();
+}
+
+/** Expect `promise` to fail with the named FundError (or Anchor constraint error). */
+export async function expectFundError(promise: Promise<unknown>, code: string): Promise<void> {
+  try {
+    await promise;
+  } catch (e: any) {
+    const err = e instanceof anchor.AnchorError ? e : anchor.AnchorError.parse(e.logs ?? []);
+    const actual = err?.error.errorCode.code;
+    if (actual !== code) {
+      throw new Error(`expected ${code}, got ${actual ?? e}`);
+    }
+    return;
+  }
+  throw new Error(`expected ${code}, but the transaction succeeded`);
+}
+
+// The legacy token program stands in for a swap router: its Transfer takes the authority at
+// index 2 (a Jupiter authority slot) and ignores trailing accounts, so a "swap" is a plain
+// transfer signed by the fund PDA, padded to the Jupiter adapter's minimum account count.
+export async function approveTokenProgramRouter(): Promise<PublicKey> {
+  const { protocolConfig } = await ensureProtocolConfig();
+  const swapProgram = pda([Buffer.from("swap_program"), TOKEN_PROGRAM_ID.toBuffer()]);
+  await program.methods
+    .setSwapProgram({ jupiter: {} }, true)
+    .accountsPartial({
+      protocolConfig,
+      swapProgram,
+      program: TOKEN_PROGRAM_ID,
+      authority: provider.wallet.publicKey,
+    })
+    .rpc();
+  return swapProgram;
+}
+
+/** Router accounts for a token-program "swap" moving `amount` from `source` to `destination`. */
+export function transferRoute(
+  source: PublicKey,
+  destination: PublicKey,
+  fund: PublicKey,
+  amount: number | bigint,
+  extraWritable: PublicKey[] = []
+): { data: Buffer; accounts: AccountMeta[] } {
+  const ix = createTransferInstruction(source, destination, fund, amount);
+  const accounts: AccountMeta[] = ix.keys.map((k) => ({ ...k, isSigner: false }));
+  for (const pubkey of extraWritable) {
+    accounts.push({ pubkey, isSigner: false, isWritable: true });
+  }
+  while (accounts.length < 9) {
+    accounts.push({ pubkey: SystemProgram.programId, isSigner: false, isWritable: false });
+  }
+  return { data: Buffer.from(ix.data), accounts };
+}
+
+/** Create a legacy SPL mint and put `amount` of it in the fund's ATA. */
+export async function fundHolding(f: FundAccounts, amount: number) {
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const mint = await createMint(connection, payer, payer.publicKey, null, 6);
+  const ata = await getOrCreateAssociatedTokenAccount(connection, payer, mint, f.fund, true);
+  await mintTo(connection, payer, mint, ata.address, payer, amount);
+  return { mint, ata: ata.address };
+}
+
+/**
+ * Register `mint` in the fund's VaultPosition registry through token_swap_vault: the "swap"
+ * sends one base unit from the WSOL vault to `sink` and outputs into the fund's ATA of `mint`.
+ */
+export async function registerPosition(
+  f: FundAccounts,
+  swapProgram: PublicKey,
+  holding: { mint: PublicKey; ata: PublicKey },
+  sink: PublicKey
+) {
+  const fund = await program.account.fund.fetch(f.fund);
+  const route = transferRoute(f.vault, sink, f.fund, 1, [holding.ata]);
+  await program.methods
+    .tokenSwapVault(route.data, new BN(1), new BN(0))
+    .accountsPartial({
+      fund: f.fund,
+      manager: f.manager.publicKey,
+      inputMint: f.baseMint,
+      fundInputAccount: f.vault,
+      outputMint: holding.mint,
+      fundOutputAccount: holding.ata,
+      outputPosition: vaultPositionAddress(f.fund, holding.mint),
+      trade: pda([Buffer.from("trade"), f.fund.toBuffer(), fund.tradeCount.toArrayLike(Buffer, "le", 8)]),
+      riskPolicy: null,
+      inputPriceFeed: null,
+      outputPriceFeed: null,
+      swapProgram,
+      routerProgram: TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .remainingAccounts(route.accounts)
+    .signers([f.manager])
+    .rpc();
+}
+
+export const vaultPositionAddress = (fund: PublicKey, mint: PublicKey) =>
+  pda([Buffer.from("vault_position"), fund.toBuffer(), mint.toBuffer()]);
+
+export const mintProgressAddress = (withdrawal: PublicKey, mint: PublicKey) =>
+  pda([Buffer.from("withdrawal_mint"), withdrawal.toBuffer(), mint.toBuffer()]);
@@ -0,0 +1,13 @@
+import { expect } from "chai";
+import { createWsolFund, expectFundError, fundedKeypair, program } from "./helpers";
+
+describe("initialize_fund fee bounds", () => {
+  it("rejects a performance fee above 50%", async () => {
+    await expectFundError(createWsolFund(await fundedKeypair(), 5001), "InvalidFee");
+  });
+
+  it("accepts a performance fee of exactly 50%", async () => {
+    const f = await createWsolFund(await fundedKeypair(), 5000);
+    expect((await program.account.fund.fetch(f.fund)).performanceFee).to.equal(5000);
+  });
+});
@@ -0,0 +1,81 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID, getMint, getTransferHook } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  FundAccounts,
+  TOKEN_METADATA_PROGRAM_ID,
+  connection,
+  createWsolFund,
+  expectFundError,
+  fundedKeypair,
+  program,
+  shareMetadataAddress,
+} from "./helpers";
+
+// Needs the Token Metadata program in the local validator: run scripts/fetch-test-fixtures.sh first.
+
+/** update_authority, mint and the NUL-trimmed strings of a Token Metadata account. */
+async function fetchMetadata(address: PublicKey) {
+  const info = await connection.getAccountInfo(address);
+  expect(info, "metadata account").to.not.equal(null);
+  expect(info!.owner.equals(TOKEN_METADATA_PROGRAM_ID)).to.equal(true);
+  const data = info!.data;
+  let offset = 1; // key
+  const updateAuthority = new PublicKey(data.subarray(offset, offset + 32));
+  offset += 32;
+  const mint = new PublicKey(data.subarray(offset, offset + 32));
+  offset += 32;
+  const readString = () => {
+    const len = data.readUInt32LE(offset);
+    const value = data.subarray(offset + 4, offset + 4 + len).toString("utf8").replace(/\0+$/, "");
+    offset += 4 + len;
+    return value;
+  };
+  return { updateAuthority, mint, name: readString(), symbol: readString(), uri: readString() };
+}
+
+describe("share metadata", () => {
+  let f: FundAccounts;
+
+  before(async () => {
+    f = await createWsolFund(await fundedKeypair(), 2000, { symbol: "DFND", uri: "https://example.com/v1.json" });
+  });
+
+  const updateUri = (uri: string, manager: Keypair) =>
+    program.methods
+      .updateShareMetadataUri(uri)
+      .accountsPartial({
+        fund: f.fund,
+        shareMetadata: shareMetadataAddress(f.sharesMint),
+        manager: manager.publicKey,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([manager])
+      .rpc();
+
+  it("creates metadata for the Token-2022 shares mint with its transfer hook", async () => {
+    const mint = await getMint(connection, f.sharesMint, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(getTransferHook(mint)!.programId.equals(program.programId)).to.equal(true);
+
+    const metadata = await fetchMetadata(shareMetadataAddress(f.sharesMint));
+    expect(metadata.mint.equals(f.sharesMint)).to.equal(true);
+    expect(metadata.updateAuthority.equals(f.fund)).to.equal(true);
+    expect(metadata.symbol).to.equal("DFND");
+    expect(metadata.uri).to.equal("https://example.com/v1.json");
+  });
+
+  it("lets the manager update the URI and keeps name and symbol", async () => {
+    const before = await fetchMetadata(shareMetadataAddress(f.sharesMint));
+    await updateUri("https://example.com/v2.json", f.manager);
+
+    const after = await fetchMetadata(shareMetadataAddress(f.sharesMint));
+    expect(after.uri).to.equal("https://example.com/v2.json");
+    expect(after.name).to.equal(before.name);
+    expect(after.symbol).to.equal(before.symbol);
+  });
+
+  it("rejects a URI update signed by anyone but the manager", async () => {
+    await expectFundError(updateUri("https://example.com/evil.json", await fundedKeypair()), "ConstraintHasOne");
+    expect((await fetchMetadata(shareMetadataAddress(f.sharesMint))).uri).to.equal("https://example.com/v2.json");
+  });
+});
@@ -0,0 +1,128 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, Transaction, sendAndConfirmTransaction } from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotent,
+  createTransferCheckedWithTransferHookInstruction,
+  getAccount,
+  getMint,
+  getTransferHook,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  FundAccounts,
+  connection,
+  createWsolFund,
+  depositSol,
+  expectFundError,
+  fundedKeypair,
+  positionAddress,
+  program,
+  provider,
+  sharesAta,
+  withdrawalAddress,
+} from "./helpers";
+
+describe("transfer_shares", () => {
+  let f: FundAccounts;
+  let sender: Keypair;
+
+  before(async () => {
+    f = await createWsolFund(await fundedKeypair());
+    sender = await fundedKeypair();
+    await depositSol(f, sender, LAMPORTS_PER_SOL);
+  });
+
+  it("opens the position of a wallet that has none and moves the basis with the shares", async () => {
+    const receiver = Keypair.generate().publicKey;
+    expect(await connection.getAccountInfo(positionAddress(f.fund, receiver))).to.equal(null);
+    const before = await program.account.investorPosition.fetch(positionAddress(f.fund, sender.publicKey));
+    const amount = before.shares.divn(4);
+
+    await program.methods
+      .transferShares(amount)
+      .accountsPartial({
+        fund: f.fund,
+        sharesMint: f.sharesMint,
+        senderSharesAccount: sharesAta(f, sender.publicKey),
+        senderPosition: positionAddress(f.fund, sender.publicKey),
+        senderWithdrawal: withdrawalAddress(f.fund, sender.publicKey),
+        receiver,
+        receiverSharesAccount: sharesAta(f, receiver),
+        receiverPosition: positionAddress(f.fund, receiver),
+        sender: sender.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([sender])
+      .rpc();
+
+    const received = await program.account.investorPosition.fetch(positionAddress(f.fund, receiver));
+    const after = await program.account.investorPosition.fetch(positionAddress(f.fund, sender.publicKey));
+    expect(received.investor.equals(receiver)).to.equal(true);
+    expect(received.shares.toString()).to.equal(amount.toString());
+    expect(received.highWaterMark.toString()).to.equal(before.highWaterMark.toString());
+    expect(after.shares.toString()).to.equal(before.shares.sub(amount).toString());
+    expect(received.totalDeposited.add(after.totalDeposited).toString()).to.equal(before.totalDeposited.toString());
+
+    const receiverShares = await getAccount(connection, sharesAta(f, receiver), undefined, TOKEN_2022_PROGRAM_ID);
+    expect(new BN(receiverShares.amount.toString()).toString()).to.equal(amount.toString());
+  });
+
+  it("leaves the transfer hook armed afterwards", async () => {
+    const mint = await getMint(connection, f.sharesMint, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(getTransferHook(mint)!.programId.equals(program.programId)).to.equal(true);
+  });
+
+  describe("plain Token-2022 transfers", () => {
+    const plainTransfer = async (receiver: PublicKey, amount: bigint) => {
+      const mint = await getMint(connection, f.sharesMint, undefined, TOKEN_2022_PROGRAM_ID);
+      const ix = await createTransferCheckedWithTransferHookInstruction(
+        connection,
+        sharesAta(f, sender.publicKey),
+        f.sharesMint,
+        sharesAta(f, receiver),
+        sender.publicKey,
+        amount,
+        mint.decimals,
+        [],
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      return sendAndConfirmTransaction(connection, new Transaction().add(ix), [sender]);
+    };
+
+    it("rejects a transfer to a wallet that has no position", async () => {
+      const receiver = Keypair.generate().publicKey;
+      const payer = (provider.wallet as any).payer as Keypair;
+      await createAssociatedTokenAccountIdempotent(connection, payer, f.sharesMint, receiver, {}, TOKEN_2022_PROGRAM_ID);
+      const sharesBefore = (await getAccount(connection, sharesAta(f, sender.publicKey), undefined, TOKEN_2022_PROGRAM_ID)).amount;
+
+      await expectFundError(plainTransfer(receiver, 1n), "AccountNotInitialized");
+
+      const sharesAfter = (await getAccount(connection, sharesAta(f, sender.publicKey), undefined, TOKEN_2022_PROGRAM_ID)).amount;
+      expect(sharesAfter).to.equal(sharesBefore);
+      expect((await getAccount(connection, sharesAta(f, receiver), undefined, TOKEN_2022_PROGRAM_ID)).amount).to.equal(0n);
+    });
+
+    it("succeeds once open_position has run for the receiver", async () => {
+      const receiver = Keypair.generate().publicKey;
+      const payer = (provider.wallet as any).payer as Keypair;
+      await createAssociatedTokenAccountIdempotent(connection, payer, f.sharesMint, receiver, {}, TOKEN_2022_PROGRAM_ID);
+      await program.methods
+        .openPosition()
+        .accountsPartial({
+          fund: f.fund,
+          investorPosition: positionAddress(f.fund, receiver),
+          investor: receiver,
+          payer: sender.publicKey,
+        })
+        .signers([sender])
+        .rpc();
+
+      await plainTransfer(receiver, 1_000n);
+
+      const received = await program.account.investorPosition.fetch(positionAddress(f.fund, receiver));
+      expect(received.shares.toNumber()).to.equal(1_000);
+    });
+  });
+});
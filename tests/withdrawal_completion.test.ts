@@ -0,0 +1,154 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import {
+  NATIVE_MINT,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAccount,
+  getAccount,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  FundAccounts,
+  approveTokenProgramRouter,
+  connection,
+  createWsolFund,
+  depositSol,
+  ensureProtocolConfig,
+  expectFundError,
+  fundHolding,
+  fundedKeypair,
+  mintProgressAddress,
+  positionAddress,
+  program,
+  provider,
+  registerPosition,
+  sharesAta,
+  transferRoute,
+  vaultPositionAddress,
+  withdrawalAddress,
+} from "./helpers";
+
+// finalize_withdrawal scales the exit by k, the completion of the most-liquidated mint. Shares are
+// burned for that k across every mint, so a mint that was never liquidated is simply left behind.
+describe("finalize_withdrawal completion factor", () => {
+  const HOLDING = 1_000_000;
+  let f: FundAccounts;
+  let investor: Keypair;
+  let protocolConfig: Awaited<ReturnType<typeof ensureProtocolConfig>>;
+  let sold: { mint: PublicKey; ata: PublicKey };
+  let kept: { mint: PublicKey; ata: PublicKey };
+
+  before(async () => {
+    protocolConfig = await ensureProtocolConfig();
+    const swapProgram = await approveTokenProgramRouter();
+    f = await createWsolFund(await fundedKeypair());
+    investor = await fundedKeypair();
+    await depositSol(f, investor, LAMPORTS_PER_SOL);
+
+    // Two non-base holdings, registered through the manager swap path
+    const payer = (provider.wallet as any).payer as Keypair;
+    const wsolSink = await createAccount(connection, payer, NATIVE_MINT, payer.publicKey, Keypair.generate());
+    sold = await fundHolding(f, HOLDING);
+    kept = await fundHolding(f, HOLDING);
+    await registerPosition(f, swapProgram, sold, wsolSink);
+    await registerPosition(f, swapProgram, kept, wsolSink);
+
+    const position = await program.account.investorPosition.fetch(positionAddress(f.fund, investor.publicKey));
+    await program.methods
+      .initiateWithdrawal(position.shares)
+      .accountsPartial({
+        fund: f.fund,
+        investorPosition: positionAddress(f.fund, investor.publicKey),
+        withdrawalState: withdrawalAddress(f.fund, investor.publicKey),
+        investor: investor.publicKey,
+        redemptionEpoch: null,
+        sharesMint: null,
+        investorSharesAccount: null,
+        tokenProgram: null,
+      })
+      .remainingAccounts(
+        [sold, kept].flatMap((h) => [
+          { pubkey: vaultPositionAddress(f.fund, h.mint), isSigner: false, isWritable: true },
+          { pubkey: h.ata, isSigner: false, isWritable: false },
+        ])
+      )
+      .signers([investor])
+      .rpc();
+
+    // Liquidate the whole allowance of the first mint; the second is never touched
+    const withdrawal = withdrawalAddress(f.fund, investor.publicKey);
+    const ws = await program.account.withdrawalState.fetch(withdrawal);
+    const allowance = ws.allowances.find((a) => a.mint.equals(sold.mint))!.amountAllowed;
+    const soldSink = await createAccount(connection, payer, sold.mint, payer.publicKey, Keypair.generate());
+    const route = transferRoute(sold.ata, soldSink, f.fund, BigInt(allowance.toString()));
+    await program.methods
+      .withdrawSwapInstruction(route.data, allowance, new BN(0))
+      .accountsPartial({
+        fund: f.fund,
+        withdrawalState: withdrawal,
+        fundSourceAccount: sold.ata,
+        fundDestinationAccount: null,
+        mintProgress: mintProgressAddress(withdrawal, sold.mint),
+        swapProgram,
+        routerProgram: TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        investor: investor.publicKey,
+      })
+      .remainingAccounts(route.accounts)
+      .signers([investor])
+      .rpc();
+
+    await program.methods
+      .markWithdrawalReady()
+      .accountsPartial({ fund: f.fund, withdrawalState: withdrawal, investor: investor.publicKey })
+      .signers([investor])
+      .rpc();
+  });
+
+  const finalize = (progressWritable: boolean) => {
+    const withdrawal = withdrawalAddress(f.fund, investor.publicKey);
+    return program.methods
+      .finalizeWithdrawal()
+      .accountsPartial({
+        fund: f.fund,
+        investorPosition: positionAddress(f.fund, investor.publicKey),
+        sharesMint: f.sharesMint,
+        investorSharesAccount: sharesAta(f, investor.publicKey),
+        withdrawalState: withdrawal,
+        investor: investor.publicKey,
+        trader: f.manager.publicKey,
+        protocolConfig: protocolConfig.protocolConfig,
+        treasury: protocolConfig.config.treasury,
+        navHistory: f.navHistory,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(
+        [sold, kept].map((h) => ({
+          pubkey: mintProgressAddress(withdrawal, h.mint),
+          isSigner: false,
+          isWritable: progressWritable,
+        }))
+      )
+      .signers([investor])
+      .rpc();
+  };
+
+  it("rejects progress accounts passed read-only", async () => {
+    await expectFundError(finalize(false), "InvalidInput");
+  });
+
+  it("burns every share for the most-complete mint and leaves the unsold mint in the fund", async () => {
+    const ws = await program.account.withdrawalState.fetch(withdrawalAddress(f.fund, investor.publicKey));
+    const sharesBefore = (await getAccount(connection, sharesAta(f, investor.publicKey), undefined, TOKEN_2022_PROGRAM_ID)).amount;
+
+    await finalize(true);
+
+    const sharesAfter = (await getAccount(connection, sharesAta(f, investor.publicKey), undefined, TOKEN_2022_PROGRAM_ID)).amount;
+    expect((sharesBefore - sharesAfter).toString()).to.equal(ws.sharesToWithdraw.toString());
+    const position = await program.account.investorPosition.fetch(positionAddress(f.fund, investor.publicKey));
+    expect(position.shares.toNumber()).to.equal(0);
+    // The investor's slice of the never-liquidated mint stays with the fund
+    expect(Number((await getAccount(connection, kept.ata)).amount)).to.equal(HOLDING);
+  });
+});